@@ -0,0 +1,182 @@
+use std::cell::RefCell;
+
+use syn::{punctuated::Punctuated, *};
+
+use crate::item::TraitVariant;
+
+// Settings of a `meta!` block, specified as inner attributes at the beginning of the block.
+//
+// Since they affect parsing, which is also performed during conversion (e.g. of inline matches),
+// they are stored in a thread-local variable while the block is being processed.
+#[derive(Clone, Default)]
+pub struct BlockConfig {
+    pub operators: OperatorPaths,
+    pub numbers: NumberPaths,
+    pub aliased_variants: Vec<AliasedVariants>,
+    // The traits that type aliases of the block declare as their bounds, e.g. `MetaNum` for
+    // `pub type Half<N: MetaNum>: MetaNum = ...`. They are used as the traits of `let` bindings
+    // that do not specify one. This is not specified by the user either.
+    pub type_traits: Vec<(Ident, Path)>,
+}
+
+// The variants of a trait defined outside of the block, in the order expected by its
+// implementation macro, for an alias of that trait. This is not specified by the user; instead,
+// the block is passed to a macro of the aliased trait, which adds the attribute
+// `#![__variants(Alias = Variant1, Variant2<N: Alias>)]`, or `#![__variants(Alias)]` if the
+// variants are not known.
+#[derive(Clone)]
+pub struct AliasedVariants {
+    pub alias: Ident,
+    pub variants: Option<Vec<TraitVariant>>,
+}
+
+// The paths of the types that type-level operators are desugared to. By default, these are the
+// corresponding types in `enum_trait_core`, with `==` comparing numbers via `meta_num::Equal`.
+// They can be changed via e.g. `#![operators(eq = meta_bool::Equal)]`.
+#[derive(Clone)]
+pub struct OperatorPaths {
+    pub add: Path,
+    pub mul: Path,
+    pub and: Path,
+    pub or: Path,
+    pub not: Path,
+    pub lt: Path,
+    pub eq: Path,
+}
+
+impl Default for OperatorPaths {
+    fn default() -> Self {
+        OperatorPaths {
+            add: parse_quote!(::enum_trait_core::meta_num::Add),
+            mul: parse_quote!(::enum_trait_core::meta_num::Mul),
+            and: parse_quote!(::enum_trait_core::meta_bool::And),
+            or: parse_quote!(::enum_trait_core::meta_bool::Or),
+            not: parse_quote!(::enum_trait_core::meta_bool::Not),
+            lt: parse_quote!(::enum_trait_core::meta_num::LessThan),
+            eq: parse_quote!(::enum_trait_core::meta_num::Equal),
+        }
+    }
+}
+
+// The paths of the types that integer literals are expanded to, e.g. `3` to
+// `Succ<Succ<Succ<Zero>>>`. By default, these are the corresponding types in `enum_trait_core`.
+// They can be changed via e.g.
+// `#![numbers(zero = meta_num::Zero, succ = meta_num::Succ)]`.
+#[derive(Clone)]
+pub struct NumberPaths {
+    pub zero: Path,
+    pub succ: Path,
+}
+
+impl Default for NumberPaths {
+    fn default() -> Self {
+        NumberPaths {
+            zero: parse_quote!(::enum_trait_core::meta_num::Zero),
+            succ: parse_quote!(::enum_trait_core::meta_num::Succ),
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT_CONFIG: RefCell<Option<BlockConfig>> = const { RefCell::new(None) };
+}
+
+impl BlockConfig {
+    pub fn parse_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut operators = OperatorPaths::default();
+        let mut numbers = NumberPaths::default();
+        let mut aliased_variants = Vec::new();
+        for attr in attrs {
+            if attr.path().is_ident("operators") {
+                attr.parse_nested_meta(|meta| {
+                    let path = if meta.path.is_ident("add") {
+                        &mut operators.add
+                    } else if meta.path.is_ident("mul") {
+                        &mut operators.mul
+                    } else if meta.path.is_ident("and") {
+                        &mut operators.and
+                    } else if meta.path.is_ident("or") {
+                        &mut operators.or
+                    } else if meta.path.is_ident("not") {
+                        &mut operators.not
+                    } else if meta.path.is_ident("lt") {
+                        &mut operators.lt
+                    } else if meta.path.is_ident("eq") {
+                        &mut operators.eq
+                    } else {
+                        return Err(meta.error(
+                            "unknown operator; expected `add`, `mul`, `and`, `or`, `not`, `lt`, or `eq`",
+                        ));
+                    };
+                    *path = meta.value()?.parse()?;
+                    Ok(())
+                })?;
+            } else if attr.path().is_ident("numbers") {
+                attr.parse_nested_meta(|meta| {
+                    let path = if meta.path.is_ident("zero") {
+                        &mut numbers.zero
+                    } else if meta.path.is_ident("succ") {
+                        &mut numbers.succ
+                    } else {
+                        return Err(meta.error("unknown number type; expected `zero` or `succ`"));
+                    };
+                    *path = meta.value()?.parse()?;
+                    Ok(())
+                })?;
+            } else if attr.path().is_ident("__variants") {
+                aliased_variants.push(attr.parse_args_with(|input: parse::ParseStream| {
+                    let alias: Ident = input.parse()?;
+                    let mut variants = None;
+                    if input.parse::<Option<Token![=]>>()?.is_some() {
+                        let parsed_variants =
+                            Punctuated::<TraitVariant, Token![,]>::parse_terminated(input)?;
+                        variants = Some(parsed_variants.into_iter().collect());
+                    }
+                    Ok(AliasedVariants { alias, variants })
+                })?);
+            } else {
+                return Err(Error::new_spanned(attr, "unknown block attribute"));
+            }
+        }
+        Ok(BlockConfig {
+            operators,
+            numbers,
+            aliased_variants,
+            type_traits: Vec::new(),
+        })
+    }
+
+    // Returns the trait declared as the bound of the type alias `ident` in this block, if any.
+    pub fn type_trait(&self, ident: &Ident) -> Option<&Path> {
+        self.type_traits
+            .iter()
+            .find(|(type_ident, _)| type_ident == ident)
+            .map(|(_, trait_path)| trait_path)
+    }
+
+    // Returns the variants of the trait aliased by `alias`, if they have been added to the block.
+    // The outer `None` means that they have not been requested yet.
+    pub fn aliased_variants(&self, alias: &Ident) -> Option<Option<&[TraitVariant]>> {
+        self.aliased_variants
+            .iter()
+            .find(|entry| &entry.alias == alias)
+            .map(|entry| entry.variants.as_deref())
+    }
+
+    // Calls `f` with `self` as the current configuration.
+    pub fn apply<R>(self, f: impl FnOnce() -> R) -> R {
+        let prev_config = CURRENT_CONFIG.with(|config| config.replace(Some(self)));
+        let result = f();
+        CURRENT_CONFIG.with(|config| config.replace(prev_config));
+        result
+    }
+
+    // Calls `f` with the current configuration, or the default configuration if no block is being
+    // processed.
+    pub fn with_current<R>(f: impl FnOnce(&BlockConfig) -> R) -> R {
+        CURRENT_CONFIG.with(|config| match &*config.borrow() {
+            Some(config) => f(config),
+            None => f(&BlockConfig::default()),
+        })
+    }
+}
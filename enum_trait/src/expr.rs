@@ -1,21 +1,46 @@
-use proc_macro2::TokenStream;
-use quote::{ToTokens, TokenStreamExt};
+use std::{iter::Peekable, vec};
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{
-    parse::{Parse, ParseBuffer, ParseStream},
+    buffer::Cursor,
+    ext::IdentExt,
+    parse::{discouraged::Speculative, Parse, ParseBuffer, ParseStream, Parser},
     punctuated::Punctuated,
+    spanned::Spanned,
+    visit_mut::VisitMut,
     *,
 };
 
-pub trait ParseExt: Parse {
-    fn parse_match_arm_body(input: ParseStream) -> Result<Self> {
+use crate::{config::*, generics::*, helpers::*, subst::*};
+
+pub trait ParseExt: Parse + Clone + Substitutable {
+    fn parse_expr(input: ParseStream) -> Result<Self> {
         input.parse()
     }
 
+    fn parse_match_arm_body(input: ParseStream) -> Result<Self> {
+        Self::parse_expr(input)
+    }
+
+    // Parses a braced branch of a type-level `if`.
+    fn parse_braced(input: ParseStream) -> Result<Self> {
+        let content: ParseBuffer;
+        braced!(content in input);
+        let result = Self::parse_expr(&content)?;
+        if !content.is_empty() {
+            return Err(content.error("unexpected token"));
+        }
+        Ok(result)
+    }
+
     fn requires_terminator(&self) -> bool {
         true
     }
 
     fn has_match_expr() -> bool;
+
+    fn visit_mut(&mut self, visitor: &mut impl VisitMut);
 }
 
 impl ParseExt for Expr {
@@ -23,6 +48,10 @@ impl ParseExt for Expr {
         input.call(Expr::parse_with_earlier_boundary_rule)
     }
 
+    fn parse_braced(input: ParseStream) -> Result<Self> {
+        Ok(Expr::Block(input.parse()?))
+    }
+
     fn requires_terminator(&self) -> bool {
         // `requires_terminator` unfortunately not exported from syn crate
         match self {
@@ -42,12 +71,28 @@ impl ParseExt for Expr {
     fn has_match_expr() -> bool {
         return true;
     }
+
+    fn visit_mut(&mut self, visitor: &mut impl VisitMut) {
+        visitor.visit_expr_mut(self);
+    }
 }
 
 impl ParseExt for Type {
+    fn parse_expr(input: ParseStream) -> Result<Self> {
+        parse_tokens_with_inline_matches(
+            input,
+            |token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == ',' || punct.as_char() == ';'),
+            parse_type_with_operators,
+        )
+    }
+
     fn has_match_expr() -> bool {
         return false;
     }
+
+    fn visit_mut(&mut self, visitor: &mut impl VisitMut) {
+        visitor.visit_type_mut(self);
+    }
 }
 
 #[derive(Clone)]
@@ -56,41 +101,819 @@ pub enum TypeLevelExpr<E, Ex = E> {
     Match(TypeLevelExprMatch<TypeLevelExpr<E>>),
 }
 
-impl<E: ParseExt, Ex> TypeLevelExpr<E, Ex> {
-    fn parse_impl(
-        input: ParseStream,
-        parse_expr: impl FnOnce(ParseStream) -> Result<Ex>,
-    ) -> Result<Self> {
-        if input.peek(Token![match]) && (!E::has_match_expr() || input.peek2(Token![<])) {
+impl<E: ParseExt> TypeLevelExpr<E> {
+    fn parse_impl(input: ParseStream, parse_expr: fn(ParseStream) -> Result<E>) -> Result<Self> {
+        if input.peek(Token![let]) && input.peek2(Ident) {
+            Self::parse_let(input, parse_expr)
+        } else if (input.peek(Token![match]) && (!E::has_match_expr() || input.peek2(Token![<])))
+            || peek_type_level_if(input)
+        {
             Ok(TypeLevelExpr::Match(input.parse()?))
         } else {
             Ok(TypeLevelExpr::Expr(parse_expr(input)?))
         }
     }
+
+    // Parses one or more type-level bindings such as `let Half: MetaNum = Div2Ceil<N>;`, followed by
+    // `in` and the expression in which they are substituted. Associated types such as
+    // `Half::ToTypeNum` are qualified with the trait. It can be omitted if the bound type is an
+    // alias defined in the same block that declares a trait as its bound.
+    fn parse_let(input: ParseStream, parse_expr: fn(ParseStream) -> Result<E>) -> Result<Self> {
+        input.parse::<Token![let]>()?;
+        let ident: Ident = input.parse()?;
+        let mut trait_path: Option<Path> = if input.parse::<Option<Token![:]>>()?.is_some() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        input.parse::<Token![=]>()?;
+        let ty = Type::parse_expr(input)?;
+        input.parse::<Token![;]>()?;
+        let mut body = if input.peek(Token![let]) {
+            Self::parse_let(input, parse_expr)?
+        } else {
+            input.parse::<Token![in]>()?;
+            Self::parse_impl(input, parse_expr)?
+        };
+        if trait_path.is_none() {
+            if let Type::Path(TypePath { qself: None, path }) = &ty {
+                if path.leading_colon.is_none() && path.segments.len() == 1 {
+                    let type_ident = &path.segments[0].ident;
+                    trait_path =
+                        BlockConfig::with_current(|config| config.type_trait(type_ident).cloned());
+                }
+            }
+        }
+        if let Some(trait_path) = &trait_path {
+            body.visit_mut(&mut QualifyTypeParamPaths {
+                ident: ident.clone(),
+                trait_path,
+            });
+        } else {
+            let mut find = FindTypeParamPaths {
+                ident: &ident,
+                found: false,
+            };
+            body.visit_mut(&mut find);
+            if find.found {
+                return Err(Error::new(
+                    ident.span(),
+                    format!("cannot infer the trait of `{ident}`; specify it, e.g. `let {ident}: Trait = ...;`"),
+                ));
+            }
+        }
+        body.substitute(
+            &GenericParam::Type(ident.into()),
+            ParamSubstArg::Arg(&GenericArgument::Type(ty)),
+        )?;
+        Ok(body)
+    }
 }
 
 impl<E: ParseExt> Parse for TypeLevelExpr<E> {
     fn parse(input: ParseStream) -> Result<Self> {
-        Self::parse_impl(input, |input| input.parse())
+        Self::parse_impl(input, E::parse_expr)
+    }
+}
+
+// Type-level matches that are nested within types are wrapped in invocations of this pseudo-macro,
+// so that the surrounding type can be parsed by syn. During conversion, each invocation is replaced
+// with a reference to an internal item.
+pub const INLINE_MATCH_MACRO_NAME: &str = "__inline_match";
+
+// Parses `T` from all tokens up to (but not including) the first token at the outermost level for
+// which `is_end` returns `true`, after wrapping type-level matches in `INLINE_MATCH_MACRO_NAME`
+// invocations.
+pub fn parse_with_inline_matches<T: Parse>(
+    input: ParseStream,
+    is_end: impl Fn(&TokenTree) -> bool,
+) -> Result<T> {
+    parse_tokens_with_inline_matches(input, is_end, T::parse)
+}
+
+fn parse_tokens_with_inline_matches<T>(
+    input: ParseStream,
+    is_end: impl Fn(&TokenTree) -> bool,
+    parser: impl Parser<Output = T>,
+) -> Result<T> {
+    let tokens = input.step(|cursor| {
+        let mut tokens = Vec::new();
+        let mut rest = *cursor;
+        let mut angle_depth = 0;
+        // The angle bracket depths of all matches whose body has not been reached yet.
+        let mut match_depths = Vec::new();
+        while let Some((token, next)) = rest.token_tree() {
+            if angle_depth == 0 && match_depths.is_empty() && is_end(&token) {
+                break;
+            }
+            match &token {
+                TokenTree::Ident(ident) if ident == "match" => {
+                    if let Some((TokenTree::Punct(punct), _)) = next.token_tree() {
+                        if punct.as_char() == '<' {
+                            match_depths.push(angle_depth);
+                        }
+                    }
+                }
+                TokenTree::Punct(punct) if punct.as_char() == '<' && has_matching_gt(next) => {
+                    angle_depth += 1
+                }
+                TokenTree::Punct(punct) if punct.as_char() == '>' && !is_arrow(&tokens) => {
+                    angle_depth -= 1
+                }
+                _ if is_brace_group(&token) && match_depths.last() == Some(&angle_depth) => {
+                    match_depths.pop();
+                }
+                _ => {}
+            }
+            tokens.push(token);
+            rest = next;
+        }
+        Ok((tokens, rest))
+    })?;
+    parser.parse2(wrap_inline_matches(tokens, false))
+}
+
+// Checks whether a `<` token followed by `rest` is closed by a matching `>` token within the same
+// group, as opposed to being the type-level operator `<`.
+fn has_matching_gt(mut rest: Cursor) -> bool {
+    let mut angle_depth = 1;
+    let mut last = None;
+    while let Some((token, next)) = rest.token_tree() {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == '<' => angle_depth += 1,
+            TokenTree::Punct(punct) if punct.as_char() == '>' && !is_arrow(last.as_slice()) => {
+                angle_depth -= 1;
+                if angle_depth == 0 {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+        last = Some(token);
+        rest = next;
+    }
+    false
+}
+
+// Parses a type that may contain the type-level operators `+`, `*`, `&&`, `||`, `!`, `<` and
+// `==`, which are desugared to the paths configured for the current block. Operators are supported
+// at the top level, within parentheses, and within generic arguments of paths. A `<` token is only
+// treated as the start of generic arguments if they can be parsed completely, so e.g. `M < N` is a
+// comparison but `Foo<M < N>` must be written as `Foo<(M < N)>`.
+fn parse_type_with_operators(input: ParseStream) -> Result<Type> {
+    parse_binary_operators(input, 0)
+}
+
+// Parses operands and binary operators with at least the given precedence.
+fn parse_binary_operators(input: ParseStream, min_precedence: u8) -> Result<Type> {
+    let mut lhs = parse_operand(input)?;
+    while let Some((precedence, op)) = peek_binary_operator(input) {
+        if precedence < min_precedence {
+            break;
+        }
+        let span = input.span();
+        input.step(|cursor| {
+            let mut rest = *cursor;
+            for _ in 0..op.len() {
+                rest = rest.punct().unwrap().1;
+            }
+            Ok(((), rest))
+        })?;
+        let is_comparison = precedence == COMPARISON_PRECEDENCE;
+        let rhs = parse_binary_operators(input, precedence + 1)?;
+        let path = BlockConfig::with_current(|config| {
+            let paths = &config.operators;
+            match op {
+                "+" => paths.add.clone(),
+                "*" => paths.mul.clone(),
+                "&&" => paths.and.clone(),
+                "||" => paths.or.clone(),
+                "<" => paths.lt.clone(),
+                _ => paths.eq.clone(),
+            }
+        });
+        lhs = operator_type(path, span, [lhs, rhs]);
+        if is_comparison
+            && peek_binary_operator(input).map(|(precedence, _)| precedence)
+                == Some(COMPARISON_PRECEDENCE)
+        {
+            return Err(input.error("comparison operators cannot be chained"));
+        }
+    }
+    Ok(lhs)
+}
+
+const COMPARISON_PRECEDENCE: u8 = 2;
+
+fn peek_binary_operator(input: ParseStream) -> Option<(u8, &'static str)> {
+    if input.peek(Token![||]) {
+        Some((0, "||"))
+    } else if input.peek(Token![&&]) {
+        Some((1, "&&"))
+    } else if input.peek(Token![==]) {
+        Some((COMPARISON_PRECEDENCE, "=="))
+    } else if input.peek(Token![<])
+        && !input.peek(Token![<=])
+        && !input.peek(Token![<<])
+        && !input.peek(Token![<-])
+    {
+        Some((COMPARISON_PRECEDENCE, "<"))
+    } else if input.peek(Token![+]) && !input.peek(Token![+=]) {
+        Some((3, "+"))
+    } else if input.peek(Token![*]) && !input.peek(Token![*=]) {
+        Some((4, "*"))
+    } else {
+        None
+    }
+}
+
+fn operator_type<const N: usize>(mut path: Path, span: Span, operands: [Type; N]) -> Type {
+    let last_segment = path.segments.last_mut().unwrap();
+    last_segment.ident.set_span(span);
+    last_segment.arguments = PathArguments::AngleBracketed(AngleBracketedGenericArguments {
+        colon2_token: None,
+        lt_token: Default::default(),
+        args: operands.into_iter().map(GenericArgument::Type).collect(),
+        gt_token: Default::default(),
+    });
+    Type::Path(TypePath { qself: None, path })
+}
+
+fn parse_operand(input: ParseStream) -> Result<Type> {
+    if input.peek(LitInt) {
+        parse_number_literal(input)
+    } else if input.peek(Token![!]) {
+        let span = input.parse::<Token![!]>()?.span;
+        let operand = parse_operand(input)?;
+        let path = BlockConfig::with_current(|config| config.operators.not.clone());
+        Ok(operator_type(path, span, [operand]))
+    } else if input.peek(token::Paren) {
+        let content: ParseBuffer;
+        let paren_token = parenthesized!(content in input);
+        let mut elems = Punctuated::new();
+        while !content.is_empty() {
+            elems.push_value(parse_type_with_operators(&content)?);
+            if content.is_empty() {
+                break;
+            }
+            elems.push_punct(content.parse()?);
+        }
+        if elems.len() == 1 && !elems.trailing_punct() {
+            // Parentheses are only used for grouping.
+            Ok(elems.into_iter().next().unwrap())
+        } else {
+            Ok(Type::Tuple(TypeTuple { paren_token, elems }))
+        }
+    } else if (input.peek(Ident::peek_any) && !input.peek2(Token![!]) && !peek_type_keyword(input))
+        || input.peek(Token![::])
+    {
+        parse_path_type(input)
+    } else if input.peek(Token![dyn]) || input.peek(Token![impl]) {
+        input.parse()
+    } else {
+        input.call(Type::without_plus)
+    }
+}
+
+// Expands an integer literal such as `3` or `3n` to the type `Succ<Succ<Succ<Zero>>>`, using the
+// paths configured for the current block. Within generic arguments, only the latter form is
+// expanded, as the former is a const argument.
+fn parse_number_literal(input: ParseStream) -> Result<Type> {
+    let lit: LitInt = input.parse()?;
+    if !matches!(lit.suffix(), "" | "n") {
+        return Err(Error::new(
+            lit.span(),
+            "type-level integer literals must not have a suffix other than `n`",
+        ));
+    }
+    let n: usize = lit.base10_parse()?;
+    let (zero, succ) = BlockConfig::with_current(|config| {
+        (config.numbers.zero.clone(), config.numbers.succ.clone())
+    });
+    let mut result = Type::Path(TypePath {
+        qself: None,
+        path: zero,
+    });
+    for _ in 0..n {
+        result = operator_type(succ.clone(), lit.span(), [result]);
+    }
+    Ok(result)
+}
+
+fn peek_type_keyword(input: ParseStream) -> bool {
+    input.peek(Token![dyn])
+        || input.peek(Token![impl])
+        || input.peek(Token![fn])
+        || input.peek(Token![unsafe])
+        || input.peek(Token![extern])
+        || input.peek(Token![for])
+}
+
+fn parse_path_type(input: ParseStream) -> Result<Type> {
+    let leading_colon: Option<Token![::]> = input.parse()?;
+    let mut segments = Punctuated::new();
+    loop {
+        let ident = input.call(Ident::parse_any)?;
+        let mut arguments = PathArguments::None;
+        if input.peek(Token![::]) && input.peek3(Token![<]) {
+            input.parse::<Token![::]>()?;
+            arguments = PathArguments::AngleBracketed(parse_generic_args(input)?);
+        } else if input.peek(Token![<]) && !input.peek(Token![<=]) {
+            let ahead = input.fork();
+            if let Ok(args) = parse_generic_args(&ahead) {
+                input.advance_to(&ahead);
+                arguments = PathArguments::AngleBracketed(args);
+            }
+        }
+        segments.push_value(PathSegment { ident, arguments });
+        if !(input.peek(Token![::]) && input.peek3(Ident::peek_any)) {
+            break;
+        }
+        segments.push_punct(input.parse()?);
+    }
+    Ok(Type::Path(TypePath {
+        qself: None,
+        path: Path {
+            leading_colon,
+            segments,
+        },
+    }))
+}
+
+fn parse_generic_args(input: ParseStream) -> Result<AngleBracketedGenericArguments> {
+    let lt_token: Token![<] = input.parse()?;
+    let mut args = Punctuated::new();
+    while !input.peek(Token![>]) {
+        args.push_value(parse_generic_arg(input)?);
+        if input.peek(Token![>]) {
+            break;
+        }
+        args.push_punct(input.parse()?);
+    }
+    let gt_token: Token![>] = input.parse()?;
+    Ok(AngleBracketedGenericArguments {
+        colon2_token: None,
+        lt_token,
+        args,
+        gt_token,
+    })
+}
+
+fn parse_generic_arg(input: ParseStream) -> Result<GenericArgument> {
+    if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+        Ok(GenericArgument::AssocType(AssocType {
+            ident: input.parse()?,
+            generics: None,
+            eq_token: input.parse()?,
+            ty: parse_type_with_operators(input)?,
+        }))
+    } else if input.peek(LitInt) && input.fork().parse::<LitInt>()?.suffix() == "n" {
+        Ok(GenericArgument::Type(parse_type_with_operators(input)?))
+    } else if input.peek(Lifetime)
+        || input.peek(Lit)
+        || input.peek(token::Brace)
+        || input.peek(Token![-])
+        || (input.peek(Ident) && (input.peek2(Token![:]) && !input.peek2(Token![::])))
+    {
+        input.parse()
+    } else {
+        Ok(GenericArgument::Type(parse_type_with_operators(input)?))
+    }
+}
+
+pub fn is_brace_group(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace)
+}
+
+fn is_arrow(prev_tokens: &[TokenTree]) -> bool {
+    matches!(
+        prev_tokens.last(),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '-' || punct.as_char() == '='
+    )
+}
+
+// Wraps type-level matches and `if` expressions in `INLINE_MATCH_MACRO_NAME` invocations. Within
+// the body of another type-level match (`in_match_body`), those that directly form the body of an
+// arm are left as they are, as they are parsed as nested matches.
+fn wrap_inline_matches(tokens: Vec<TokenTree>, in_match_body: bool) -> TokenStream {
+    let mut result = TokenStream::new();
+    let mut tokens = tokens.into_iter().peekable();
+    let mut after_eq = false;
+    let mut after_fat_arrow = false;
+    while let Some(token) = tokens.next() {
+        let is_fat_arrow =
+            after_eq && matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '>');
+        after_eq = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '=');
+        match token {
+            TokenTree::Ident(ident) if ident == INLINE_MATCH_MACRO_NAME => {
+                // Already wrapped, e.g. when parsing the result of a substitution.
+                result.append(ident);
+                result.extend(tokens.by_ref().take(2));
+            }
+            TokenTree::Ident(ident)
+                if (ident == "match" || ident == "if")
+                    && type_level_scrutinee_len(tokens.clone()).is_some() =>
+            {
+                let expr_tokens = take_type_level_expr(ident, &mut tokens);
+                if in_match_body && after_fat_arrow {
+                    result.extend(expr_tokens);
+                } else {
+                    let macro_ident = Ident::new(INLINE_MATCH_MACRO_NAME, expr_tokens[0].span());
+                    result.extend(quote!(#macro_ident!{#(#expr_tokens)*}));
+                }
+            }
+            TokenTree::Group(group) => result.append(wrap_inline_matches_in_group(&group, false)),
+            token => result.append(token),
+        }
+        after_fat_arrow = is_fat_arrow;
+    }
+    result
+}
+
+fn wrap_inline_matches_in_group(group: &Group, in_match_body: bool) -> Group {
+    let mut new_group = Group::new(
+        group.delimiter(),
+        wrap_inline_matches(group.stream().into_iter().collect(), in_match_body),
+    );
+    new_group.set_span(group.span());
+    new_group
+}
+
+// Returns the number of tokens following an `if` or `match` token that form a type-level
+// scrutinee `<...>`, i.e. the tokens up to and including the `>` token that is directly followed
+// by the body, or `None` if they form a regular condition or scrutinee such as
+// `<T as Trait>::VALUE`. As the scrutinee may contain comparisons such as `N < M`, we cannot rely
+// on matching angle brackets, but a brace group can only follow `<` or `,` within it, as a const
+// argument.
+fn type_level_scrutinee_len(mut tokens: impl Iterator<Item = TokenTree>) -> Option<usize> {
+    let Some(TokenTree::Punct(punct)) = tokens.next() else {
+        return None;
+    };
+    if punct.as_char() != '<' {
+        return None;
+    }
+    let mut prev_tokens = vec![TokenTree::Punct(punct)];
+    for token in tokens {
+        if is_brace_group(&token) {
+            match prev_tokens.last() {
+                Some(TokenTree::Punct(punct))
+                    if punct.as_char() == '>'
+                        && !is_arrow(&prev_tokens[..prev_tokens.len() - 1]) =>
+                {
+                    return Some(prev_tokens.len());
+                }
+                Some(TokenTree::Punct(punct))
+                    if punct.as_char() == '<' || punct.as_char() == ',' => {}
+                _ => return None,
+            }
+        } else if matches!(&token, TokenTree::Punct(punct) if punct.as_char() == ';') {
+            return None;
+        }
+        prev_tokens.push(token);
     }
+    None
 }
 
-impl<E: ParseExt> Parse for TypeLevelExpr<E, Block> {
+// Takes all tokens of a type-level match or `if` expression (including all `else` branches, which
+// may also have regular conditions), wrapping nested matches within bodies and branches.
+fn take_type_level_expr(
+    first: Ident,
+    tokens: &mut Peekable<vec::IntoIter<TokenTree>>,
+) -> Vec<TokenTree> {
+    let is_match = first == "match";
+    let mut result = vec![TokenTree::Ident(first)];
+    let mut scrutinee_len = type_level_scrutinee_len(tokens.clone());
+    loop {
+        if let Some(scrutinee_len) = scrutinee_len {
+            result.extend(tokens.by_ref().take(scrutinee_len));
+        }
+        for token in tokens.by_ref() {
+            if let TokenTree::Group(group) = &token {
+                if group.delimiter() == Delimiter::Brace {
+                    result.push(TokenTree::Group(wrap_inline_matches_in_group(
+                        group, is_match,
+                    )));
+                    break;
+                }
+            }
+            result.push(token);
+        }
+        if is_match || !matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident == "else") {
+            return result;
+        }
+        result.extend(tokens.next());
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident == "if" => {
+                scrutinee_len = type_level_scrutinee_len(tokens.clone());
+                result.push(TokenTree::Ident(ident));
+            }
+            Some(TokenTree::Group(group)) => {
+                result.push(TokenTree::Group(wrap_inline_matches_in_group(
+                    &group, false,
+                )));
+                return result;
+            }
+            token => {
+                result.extend(token);
+                return result;
+            }
+        }
+    }
+}
+
+impl Parse for TypeLevelExpr<Expr, Block> {
     fn parse(input: ParseStream) -> Result<Self> {
-        Self::parse_impl(input, |input| {
-            Ok(Block {
-                brace_token: Default::default(),
-                stmts: input.call(Block::parse_within)?,
-            })
+        let tokens: TokenStream = input.parse()?;
+        let stmts =
+            Block::parse_within.parse2(wrap_inline_matches(tokens.into_iter().collect(), false))?;
+        hoist_inline_match(Block {
+            brace_token: Default::default(),
+            stmts,
         })
     }
 }
 
+// Type-level matches can appear anywhere within a function body. The first match is moved to the
+// top level, with each arm containing a copy of the entire block in which the match is replaced by
+// the body of the arm. This way, the surrounding code is type-checked separately for each arm.
+fn hoist_inline_match(mut block: Block) -> Result<TypeLevelExpr<Expr, Block>> {
+    if let [Stmt::Macro(StmtMacro {
+        mac,
+        semi_token: None,
+        ..
+    })] = block.stmts.as_slice()
+    {
+        if mac.path.is_ident(INLINE_MATCH_MACRO_NAME) {
+            // The entire block is a match, so it does not need to be copied.
+            let mut match_expr: TypeLevelExprMatch<TypeLevelExpr<Expr>> = mac.parse_body()?;
+            hoist_inline_matches_in_arms(&mut match_expr)?;
+            return Ok(TypeLevelExpr::Match(match_expr));
+        }
+    }
+    let mut replace = ReplaceInlineMatch::default();
+    replace.visit_block_mut(&mut block);
+    if let Some(errors) = replace.errors {
+        return Err(errors);
+    }
+    match replace.found {
+        None => Ok(TypeLevelExpr::Expr(block)),
+        Some(InlineMatch::Expr(mac)) => Ok(TypeLevelExpr::Match(hoist_arms(
+            &block,
+            mac.parse_body::<TypeLevelExprMatch<TypeLevelExpr<Expr>>>()?,
+            |body| {
+                InlineMatchReplacement::Expr(match body {
+                    TypeLevelExpr::Expr(expr) => expr,
+                    TypeLevelExpr::Match(match_expr) => Expr::Macro(ExprMacro {
+                        attrs: Vec::new(),
+                        mac: inline_match_macro(match_expr),
+                    }),
+                })
+            },
+        )?)),
+        Some(InlineMatch::Type(mac)) => Ok(TypeLevelExpr::Match(hoist_arms(
+            &block,
+            mac.parse_body::<TypeLevelExprMatch<TypeLevelExpr<Type>>>()?,
+            |body| {
+                InlineMatchReplacement::Type(match body {
+                    TypeLevelExpr::Expr(ty) => ty,
+                    TypeLevelExpr::Match(match_expr) => Type::Macro(TypeMacro {
+                        mac: inline_match_macro(match_expr),
+                    }),
+                })
+            },
+        )?)),
+    }
+}
+
+fn hoist_inline_matches_in_arms(
+    match_expr: &mut TypeLevelExprMatch<TypeLevelExpr<Expr>>,
+) -> Result<()> {
+    for arm in &mut match_expr.arms {
+        match &mut arm.body {
+            TypeLevelExpr::Expr(expr) => {
+                let block = Block {
+                    brace_token: Default::default(),
+                    stmts: vec![Stmt::Expr(expr.clone(), None)],
+                };
+                if let TypeLevelExpr::Match(hoisted_match_expr) = hoist_inline_match(block)? {
+                    arm.body = TypeLevelExpr::Match(hoisted_match_expr);
+                }
+            }
+            TypeLevelExpr::Match(nested_match_expr) => {
+                hoist_inline_matches_in_arms(nested_match_expr)?
+            }
+        }
+    }
+    Ok(())
+}
+
+fn hoist_arms<E>(
+    block: &Block,
+    match_expr: TypeLevelExprMatch<TypeLevelExpr<E>>,
+    replacement: impl Fn(TypeLevelExpr<E>) -> InlineMatchReplacement,
+) -> Result<TypeLevelExprMatch<TypeLevelExpr<Expr>>> {
+    let mut arms = Vec::new();
+    for arm in match_expr.arms {
+        let mut block = block.clone();
+        let mut replace = ReplaceInlineMatch {
+            replacement: Some(replacement(arm.body)),
+            ..Default::default()
+        };
+        replace.visit_block_mut(&mut block);
+        let body = match hoist_inline_match(block)? {
+            TypeLevelExpr::Expr(block) => TypeLevelExpr::Expr(Expr::Block(ExprBlock {
+                attrs: Vec::new(),
+                label: None,
+                block,
+            })),
+            TypeLevelExpr::Match(match_expr) => TypeLevelExpr::Match(match_expr),
+        };
+        arms.push(TypeLevelArm {
+            selectors: arm.selectors,
+            guard: arm.guard,
+            fat_arrow_token: arm.fat_arrow_token,
+            body,
+            comma_token: Some(arm.comma_token.unwrap_or_default()),
+        });
+    }
+    Ok(TypeLevelExprMatch {
+        match_token: match_expr.match_token,
+        lt_token: match_expr.lt_token,
+        types: match_expr.types,
+        gt_token: match_expr.gt_token,
+        brace_token: match_expr.brace_token,
+        arms,
+        guard_if_token: match_expr.guard_if_token,
+    })
+}
+
+fn inline_match_macro<E: ToTokens>(match_expr: TypeLevelExprMatch<E>) -> Macro {
+    Macro {
+        path: Ident::new(INLINE_MATCH_MACRO_NAME, match_expr.match_token.span).into(),
+        bang_token: Default::default(),
+        delimiter: MacroDelimiter::Brace(Default::default()),
+        tokens: match_expr.into_token_stream(),
+    }
+}
+
+enum InlineMatch {
+    Expr(Macro),
+    Type(Macro),
+}
+
+enum InlineMatchReplacement {
+    Expr(Expr),
+    Type(Type),
+}
+
+// Finds the first type-level match within a block (in source order), and replaces it with
+// `replacement` if specified. Matches within other matches are not considered, as they are part of
+// the macro tokens.
+#[derive(Default)]
+struct ReplaceInlineMatch {
+    replacement: Option<InlineMatchReplacement>,
+    found: Option<InlineMatch>,
+    errors: Option<Error>,
+}
+
+impl VisitMut for ReplaceInlineMatch {
+    fn visit_stmt_mut(&mut self, i: &mut Stmt) {
+        if let Stmt::Macro(StmtMacro {
+            mac, semi_token, ..
+        }) = i
+        {
+            if mac.path.is_ident(INLINE_MATCH_MACRO_NAME) {
+                if self.found.is_none() {
+                    self.found = Some(InlineMatch::Expr(mac.clone()));
+                    if let Some(InlineMatchReplacement::Expr(expr)) = self.replacement.take() {
+                        let expr = if semi_token.is_none() && expr.requires_terminator() {
+                            Expr::Block(ExprBlock {
+                                attrs: Vec::new(),
+                                label: None,
+                                block: Block {
+                                    brace_token: Default::default(),
+                                    stmts: vec![Stmt::Expr(expr, None)],
+                                },
+                            })
+                        } else {
+                            expr
+                        };
+                        *i = Stmt::Expr(expr, *semi_token);
+                    }
+                }
+                return;
+            }
+        }
+        visit_mut::visit_stmt_mut(self, i)
+    }
+
+    fn visit_expr_mut(&mut self, i: &mut Expr) {
+        if let Expr::Macro(ExprMacro { mac, .. }) = i {
+            if mac.path.is_ident(INLINE_MATCH_MACRO_NAME) {
+                if self.found.is_none() {
+                    self.found = Some(InlineMatch::Expr(mac.clone()));
+                    if let Some(InlineMatchReplacement::Expr(expr)) = self.replacement.take() {
+                        *i = parenthesize(expr);
+                    }
+                }
+                return;
+            }
+        }
+        visit_mut::visit_expr_mut(self, i)
+    }
+
+    fn visit_type_mut(&mut self, i: &mut Type) {
+        if let Type::Macro(TypeMacro { mac }) = i {
+            if mac.path.is_ident(INLINE_MATCH_MACRO_NAME) {
+                if self.found.is_none() {
+                    self.found = Some(InlineMatch::Type(mac.clone()));
+                    if let Some(InlineMatchReplacement::Type(ty)) = self.replacement.take() {
+                        *i = ty;
+                    }
+                }
+                return;
+            }
+        }
+        visit_mut::visit_type_mut(self, i)
+    }
+
+    fn visit_macro_mut(&mut self, i: &mut Macro) {
+        if contains_inline_match(i.tokens.clone()) {
+            add_error(
+                &mut self.errors,
+                Error::new_spanned(
+                    &i.path,
+                    "type-level matches are not supported within macro invocations",
+                ),
+            );
+        }
+    }
+
+    fn visit_item_mut(&mut self, i: &mut Item) {
+        if contains_inline_match(i.to_token_stream()) {
+            add_error(
+                &mut self.errors,
+                Error::new(
+                    i.span(),
+                    "type-level matches are not supported within nested items",
+                ),
+            );
+        }
+    }
+}
+
+pub fn contains_inline_match(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == INLINE_MATCH_MACRO_NAME,
+        TokenTree::Group(group) => contains_inline_match(group.stream()),
+        _ => false,
+    })
+}
+
+// Wraps `expr` in parentheses unless it binds at least as tightly as the macro invocation it
+// replaces.
+fn parenthesize(expr: Expr) -> Expr {
+    match expr {
+        Expr::Array(_)
+        | Expr::Block(_)
+        | Expr::Call(_)
+        | Expr::Field(_)
+        | Expr::Index(_)
+        | Expr::Lit(_)
+        | Expr::Macro(_)
+        | Expr::MethodCall(_)
+        | Expr::Paren(_)
+        | Expr::Path(_)
+        | Expr::Struct(_)
+        | Expr::Tuple(_) => expr,
+        _ => Expr::Paren(ExprParen {
+            attrs: Vec::new(),
+            paren_token: Default::default(),
+            expr: Box::new(expr),
+        }),
+    }
+}
+
 impl<E: ParseExt> ParseExt for TypeLevelExpr<E> {
     fn parse_match_arm_body(input: ParseStream) -> Result<Self> {
         Self::parse_impl(input, E::parse_match_arm_body)
     }
 
+    fn parse_braced(input: ParseStream) -> Result<Self> {
+        if E::has_match_expr() {
+            // A regular block, which may not start with a type-level `let`.
+            Ok(TypeLevelExpr::Expr(E::parse_braced(input)?))
+        } else {
+            let content: ParseBuffer;
+            braced!(content in input);
+            let result = content.parse()?;
+            if !content.is_empty() {
+                return Err(content.error("unexpected token"));
+            }
+            Ok(result)
+        }
+    }
+
     fn requires_terminator(&self) -> bool {
         match self {
             TypeLevelExpr::Expr(expr) => expr.requires_terminator(),
@@ -101,6 +924,23 @@ impl<E: ParseExt> ParseExt for TypeLevelExpr<E> {
     fn has_match_expr() -> bool {
         return true;
     }
+
+    fn visit_mut(&mut self, visitor: &mut impl VisitMut) {
+        match self {
+            TypeLevelExpr::Expr(expr) => expr.visit_mut(visitor),
+            TypeLevelExpr::Match(match_expr) => {
+                for match_type in &mut match_expr.types {
+                    visitor.visit_type_mut(&mut match_type.ty);
+                }
+                for arm in &mut match_expr.arms {
+                    if let Some((_, guard)) = &mut arm.guard {
+                        visitor.visit_type_mut(guard);
+                    }
+                    arm.body.visit_mut(visitor);
+                }
+            }
+        }
+    }
 }
 
 impl<E: ToTokens> ToTokens for TypeLevelExpr<E> {
@@ -116,24 +956,128 @@ impl<E: ToTokens> ToTokens for TypeLevelExpr<E> {
 pub struct TypeLevelExprMatch<E> {
     pub match_token: token::Match,
     pub lt_token: token::Lt,
-    pub types: Punctuated<Type, token::Comma>,
+    pub types: Punctuated<TypeLevelMatchType, token::Comma>,
     pub gt_token: token::Gt,
     pub brace_token: token::Brace,
     pub arms: Vec<TypeLevelArm<E>>,
+    // The `if` token of the match guard that this match was lowered from, if any. Such matches
+    // only exist during conversion, so this is not part of the token representation.
+    pub guard_if_token: Option<token::If>,
+}
+
+impl<E> TypeLevelExprMatch<TypeLevelExpr<E>> {
+    // Calls `f` on the bodies of all arms, including the arms of nested matches.
+    pub fn visit_arm_bodies_mut(&mut self, f: &mut impl FnMut(&mut E)) {
+        for arm in &mut self.arms {
+            match &mut arm.body {
+                TypeLevelExpr::Expr(expr) => f(expr),
+                TypeLevelExpr::Match(match_expr) => match_expr.visit_arm_bodies_mut(f),
+            }
+        }
+    }
+}
+
+impl<E: ParseExt> TypeLevelExprMatch<E> {
+    // Parses `if <Cond> { A } else { B }` as `match <Cond> { False => B, True => A }`. The `else`
+    // branch may also be another `if` expression, which is parsed as an arm body.
+    fn parse_if(input: ParseStream) -> Result<Self> {
+        let if_token: Token![if] = input.parse()?;
+        let (lt_token, types, gt_token) = parse_scrutinee(input)?;
+        if types.len() != 1 {
+            return Err(Error::new(types.span(), "expected a single condition"));
+        }
+        let then_branch = E::parse_braced(input)?;
+        let else_token: Token![else] = input.parse()?;
+        let else_branch = if input.peek(Token![if]) {
+            E::parse_match_arm_body(input)?
+        } else {
+            E::parse_braced(input)?
+        };
+        let arm = |variant_name: &str, span: Span, body: E| TypeLevelArm {
+            selectors: Punctuated::from_iter([TypeLevelArmSelector::Specific {
+                ident: Ident::new(variant_name, span),
+                generics: Generics::default(),
+                nested: Vec::new(),
+            }]),
+            guard: None,
+            fat_arrow_token: Default::default(),
+            body,
+            comma_token: Some(Default::default()),
+        };
+        Ok(TypeLevelExprMatch {
+            match_token: Token![match](if_token.span),
+            lt_token,
+            types,
+            gt_token,
+            brace_token: Default::default(),
+            arms: vec![
+                arm("False", else_token.span, else_branch),
+                arm("True", if_token.span, then_branch),
+            ],
+            guard_if_token: None,
+        })
+    }
+}
+
+// Checks whether the input starts with a type-level `if`, as opposed to a regular `if` with a
+// condition such as `<T as Trait>::VALUE`.
+fn peek_type_level_if(input: ParseStream) -> bool {
+    let ahead = input.fork();
+    ahead.parse::<Token![if]>().is_ok()
+        && parse_scrutinee(&ahead).is_ok()
+        && ahead.peek(token::Brace)
+}
+
+// Parses the types to match on, enclosed in `<...>`. The closing `>` is the one that is directly
+// followed by the body, so that the types may contain comparisons such as `N < M`, which would
+// otherwise be parsed as the start of generic arguments. Like any other scrutinee, the result of
+// an operator can only be matched on if its bound is a trait (alias) of the same block, so this is
+// mostly useful with operators that are configured to map to types of that block.
+fn parse_scrutinee(
+    input: ParseStream,
+) -> Result<(
+    token::Lt,
+    Punctuated<TypeLevelMatchType, Token![,]>,
+    token::Gt,
+)> {
+    let lt_token: Token![<] = input.parse()?;
+    let types_tokens = input.step(|cursor| {
+        let mut tokens = Vec::new();
+        let mut rest = *cursor;
+        while let Some((token, next)) = rest.token_tree() {
+            if matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '>')
+                && !is_arrow(&tokens)
+                && next
+                    .token_tree()
+                    .is_some_and(|(next_token, _)| is_brace_group(&next_token))
+            {
+                return Ok((TokenStream::from_iter(tokens), rest));
+            }
+            tokens.push(token);
+            rest = next;
+        }
+        Err(cursor.error("expected `>` followed by a block"))
+    })?;
+    let types = Punctuated::parse_terminated.parse2(types_tokens)?;
+    if types.is_empty() {
+        return Err(input.error("expected a type"));
+    }
+    let gt_token: Token![>] = input.parse()?;
+    Ok((lt_token, types, gt_token))
 }
 
 impl<E: ParseExt> Parse for TypeLevelExprMatch<E> {
     fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![if]) {
+            return Self::parse_if(input);
+        }
         let match_token: Token![match] = input.parse()?;
-        let lt_token: Token![<] = input.parse()?;
-        let types: Punctuated<Type, Token![,]> = Punctuated::parse_separated_nonempty(input)?;
-        input.parse::<Option<Token![,]>>()?;
-        let gt_token: Token![>] = input.parse()?;
+        let (lt_token, types, gt_token) = parse_scrutinee(input)?;
         let content: ParseBuffer;
         let brace_token = braced!(content in input);
         let mut arms = Vec::new();
         while !content.is_empty() {
-            arms.push(content.call(TypeLevelArm::parse)?);
+            arms.extend(content.call(TypeLevelArm::parse_alternatives)?);
         }
         Ok(TypeLevelExprMatch {
             match_token,
@@ -142,6 +1086,7 @@ impl<E: ParseExt> Parse for TypeLevelExprMatch<E> {
             gt_token,
             brace_token,
             arms,
+            guard_if_token: None,
         })
     }
 }
@@ -158,19 +1103,75 @@ impl<E: ToTokens> ToTokens for TypeLevelExprMatch<E> {
     }
 }
 
+// A type to match on, optionally annotated with the trait to use for matching. The annotation is
+// required if the type is not a variable and the trait cannot be inferred from the match arms.
+#[derive(Clone)]
+pub struct TypeLevelMatchType {
+    pub ty: Type,
+    pub colon_token: Option<token::Colon>,
+    pub bounds: TypeParamBounds,
+}
+
+impl Parse for TypeLevelMatchType {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ty = parse_type_with_operators(input)?;
+        let colon_token: Option<Token![:]> = input.parse()?;
+        let bounds = if colon_token.is_some() {
+            parse_type_param_bounds(input)?
+        } else {
+            Punctuated::new()
+        };
+        Ok(TypeLevelMatchType {
+            ty,
+            colon_token,
+            bounds,
+        })
+    }
+}
+
+impl ToTokens for TypeLevelMatchType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.ty.to_tokens(tokens);
+        self.colon_token.to_tokens(tokens);
+        self.bounds.to_tokens(tokens);
+    }
+}
+
 #[derive(Clone)]
 pub struct TypeLevelArm<E> {
     pub selectors: Punctuated<TypeLevelArmSelector, token::Comma>,
+    // A type that implements an alias of `MetaBool` defined in the same block (or `MetaBool` itself
+    // within its own block); the arm only applies if the type is `True`.
+    pub guard: Option<(token::If, Type)>,
     pub fat_arrow_token: token::FatArrow,
     pub body: E,
     pub comma_token: Option<token::Comma>,
 }
 
-impl<E: ParseExt> Parse for TypeLevelArm<E> {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let selectors: Punctuated<TypeLevelArmSelector, Token![,]> =
-            Punctuated::parse_separated_nonempty(input)?;
-        input.parse::<Option<Token![,]>>()?;
+impl<E: ParseExt> TypeLevelArm<E> {
+    // Parses an arm whose selectors may contain or-patterns, returning one arm per combination of
+    // alternatives.
+    fn parse_alternatives(input: ParseStream) -> Result<Vec<Self>> {
+        let mut selector_lists = vec![Punctuated::new()];
+        loop {
+            let alternatives = TypeLevelArmSelector::parse_alternatives(input)?;
+            selector_lists =
+                combine_alternatives(selector_lists, &alternatives, |selectors, alternative| {
+                    selectors.push(alternative.clone())
+                });
+            if input.peek(Token![=>]) || input.peek(Token![if]) {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+            if input.peek(Token![=>]) || input.peek(Token![if]) {
+                break;
+            }
+        }
+        let guard = if let Some(if_token) = input.parse::<Option<Token![if]>>()? {
+            Some((if_token, input.parse()?))
+        } else {
+            None
+        };
         let fat_arrow_token: Token![=>] = input.parse()?;
         let body = E::parse_match_arm_body(input)?;
         let comma_token: Option<Token![,]> = if input.is_empty() {
@@ -180,18 +1181,43 @@ impl<E: ParseExt> Parse for TypeLevelArm<E> {
         } else {
             input.parse::<Option<Token![,]>>()?
         };
-        Ok(TypeLevelArm {
-            selectors,
-            fat_arrow_token,
-            body,
-            comma_token,
-        })
+        Ok(selector_lists
+            .into_iter()
+            .map(|selectors| TypeLevelArm {
+                selectors,
+                guard: guard.clone(),
+                fat_arrow_token,
+                body: body.clone(),
+                comma_token,
+            })
+            .collect())
     }
 }
 
+// Extends each of the given values by each of the given alternatives.
+fn combine_alternatives<T: Clone, A>(
+    values: Vec<T>,
+    alternatives: &[A],
+    extend: impl Fn(&mut T, &A),
+) -> Vec<T> {
+    let mut result = Vec::new();
+    for value in values {
+        for alternative in alternatives {
+            let mut value = value.clone();
+            extend(&mut value, alternative);
+            result.push(value);
+        }
+    }
+    result
+}
+
 impl<E: ToTokens> ToTokens for TypeLevelArm<E> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         self.selectors.to_tokens(tokens);
+        if let Some((if_token, guard)) = &self.guard {
+            if_token.to_tokens(tokens);
+            guard.to_tokens(tokens);
+        }
         self.fat_arrow_token.to_tokens(tokens);
         self.body.to_tokens(tokens);
         self.comma_token.to_tokens(tokens);
@@ -200,35 +1226,147 @@ impl<E: ToTokens> ToTokens for TypeLevelArm<E> {
 
 #[derive(Clone)]
 pub enum TypeLevelArmSelector {
-    Specific { ident: Ident, generics: Generics },
-    Default { underscore_token: token::Underscore },
+    // `nested` contains the selectors of nested patterns such as `Succ<N>` in `Succ<Succ<N>>`,
+    // along with the index of the generic parameter that stands in for each of them.
+    Specific {
+        ident: Ident,
+        generics: Generics,
+        nested: Vec<(usize, TypeLevelArmSelector)>,
+    },
+    Default {
+        underscore_token: token::Underscore,
+    },
 }
 
-impl Parse for TypeLevelArmSelector {
-    fn parse(input: ParseStream) -> Result<Self> {
+// The alternatives of each nested pattern, along with the index of its generic parameter.
+type NestedAlternatives = Vec<(usize, Vec<TypeLevelArmSelector>)>;
+
+impl TypeLevelArmSelector {
+    pub fn has_nested_patterns(&self) -> bool {
+        match self {
+            TypeLevelArmSelector::Specific { nested, .. } => !nested.is_empty(),
+            TypeLevelArmSelector::Default { .. } => false,
+        }
+    }
+
+    pub fn all_generics_mut(&mut self) -> Vec<&mut Generics> {
+        let mut result = Vec::new();
+        if let TypeLevelArmSelector::Specific {
+            generics, nested, ..
+        } = self
+        {
+            result.push(generics);
+            for (_, nested_selector) in nested {
+                result.extend(nested_selector.all_generics_mut());
+            }
+        }
+        result
+    }
+
+    // Parses a selector that may contain or-patterns, returning one selector per alternative.
+    fn parse_alternatives(input: ParseStream) -> Result<Vec<Self>> {
+        let mut alternatives = Vec::new();
+        loop {
+            alternatives.extend(Self::parse_single(input)?);
+            if input.parse::<Option<Token![|]>>()?.is_none() {
+                return Ok(alternatives);
+            }
+        }
+    }
+
+    fn parse_single(input: ParseStream) -> Result<Vec<Self>> {
         if let Some(underscore_token) = input.parse::<Option<Token![_]>>()? {
-            Ok(TypeLevelArmSelector::Default { underscore_token })
-        } else {
-            let mut ident: Ident;
-            input.parse::<Option<Token![::]>>()?;
-            loop {
-                ident = input.parse()?;
-                if input.parse::<Option<Token![::]>>()?.is_none() {
-                    break;
-                }
+            return Ok(vec![TypeLevelArmSelector::Default { underscore_token }]);
+        }
+        let mut ident: Ident;
+        input.parse::<Option<Token![::]>>()?;
+        loop {
+            ident = input.parse()?;
+            if input.parse::<Option<Token![::]>>()?.is_none() {
+                break;
             }
-            let generics: Generics = input.parse()?;
-            Ok(TypeLevelArmSelector::Specific { ident, generics })
         }
+        let (generics, nested_alternatives) = Self::parse_generics(input)?;
+        let mut nested_lists = vec![Vec::new()];
+        for (param_idx, alternatives) in nested_alternatives {
+            nested_lists =
+                combine_alternatives(nested_lists, &alternatives, |nested, alternative| {
+                    nested.push((param_idx, alternative.clone()))
+                });
+        }
+        Ok(nested_lists
+            .into_iter()
+            .map(|nested| TypeLevelArmSelector::Specific {
+                ident: ident.clone(),
+                generics: generics.clone(),
+                nested,
+            })
+            .collect())
+    }
+
+    fn parse_generics(input: ParseStream) -> Result<(Generics, NestedAlternatives)> {
+        let mut generics = Generics::default();
+        let mut nested = Vec::new();
+        let Some(lt_token) = input.parse::<Option<Token![<]>>()? else {
+            return Ok((generics, nested));
+        };
+        generics.lt_token = Some(lt_token);
+        while !input.peek(Token![>]) {
+            if input.peek(Lifetime) || input.peek(Token![const]) {
+                generics.params.push_value(input.parse()?);
+            } else if input.peek(Token![_])
+                || (input.peek(Ident)
+                    && (input.peek2(Token![<])
+                        || input.peek2(Token![::])
+                        || input.peek2(Token![|])))
+            {
+                let nested_alternatives = Self::parse_alternatives(input)?;
+                let placeholder = Ident::new("__Nested", nested_alternatives[0].span());
+                nested.push((generics.params.len(), nested_alternatives));
+                generics
+                    .params
+                    .push_value(GenericParam::Type(placeholder.into()));
+            } else {
+                generics
+                    .params
+                    .push_value(GenericParam::Type(input.parse()?));
+            }
+            if input.peek(Token![>]) {
+                break;
+            }
+            generics.params.push_punct(input.parse()?);
+        }
+        generics.gt_token = Some(input.parse()?);
+        Ok((generics, nested))
     }
 }
 
 impl ToTokens for TypeLevelArmSelector {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
-            TypeLevelArmSelector::Specific { ident, generics } => {
+            TypeLevelArmSelector::Specific {
+                ident,
+                generics,
+                nested,
+            } => {
                 ident.to_tokens(tokens);
-                generics.to_tokens(tokens);
+                if nested.is_empty() {
+                    generics.to_tokens(tokens);
+                } else {
+                    generics.lt_token.to_tokens(tokens);
+                    for (param_idx, pair) in generics.params.pairs().enumerate() {
+                        if let Some((_, nested_selector)) = nested
+                            .iter()
+                            .find(|(nested_idx, _)| *nested_idx == param_idx)
+                        {
+                            nested_selector.to_tokens(tokens);
+                        } else {
+                            pair.value().to_tokens(tokens);
+                        }
+                        pair.punct().to_tokens(tokens);
+                    }
+                    generics.gt_token.to_tokens(tokens);
+                }
             }
             TypeLevelArmSelector::Default { underscore_token } => {
                 underscore_token.to_tokens(tokens);
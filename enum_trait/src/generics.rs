@@ -212,6 +212,7 @@ impl MetaGenerics {
                 }
             }
         }
+        params.pop_punct();
         let (lt_token, gt_token) = if params.is_empty() {
             (None, None)
         } else {
@@ -342,9 +343,9 @@ impl MetaWhereClause {
     }
 
     pub fn extract_where_clause(&self) -> Option<WhereClause> {
-        // Omit type equalities, as we don't have a robust proof mechanism to convince Rust that
-        // they are satisfied. Instead, we trust the user to define the variant combinations
-        // equivalently to the `where` clause.
+        // Omit type equalities, as Rust would not consider them to be implied by the trait, so
+        // they would need to be proved wherever the trait is used. Instead, they are checked
+        // when implementing the trait for each variant (see `OutputItemTraitDef::to_tokens`).
         let mut predicates = Punctuated::new();
         for pair in self.predicates.pairs() {
             let (predicate, punct) = pair.into_tuple();
@@ -439,6 +440,7 @@ impl VisitMut for RemoveTypeBoundParamsFromPathArguments<'_> {
                         }
                     }
                 }
+                new_args.pop_punct();
                 if new_args.is_empty() {
                     *i = PathArguments::None;
                 } else {
@@ -451,6 +453,92 @@ impl VisitMut for RemoveTypeBoundParamsFromPathArguments<'_> {
     }
 }
 
+// Replaces all uses of `self` as a value, so that the body of a method can be moved into a
+// function that takes the receiver as a normal parameter.
+pub struct ReplaceSelfValue<'a>(pub &'a Ident);
+
+impl VisitMut for ReplaceSelfValue<'_> {
+    fn visit_expr_path_mut(&mut self, i: &mut ExprPath) {
+        if i.qself.is_none() && i.path.is_ident("self") {
+            i.path = self.0.clone().into();
+        }
+    }
+
+    fn visit_macro_mut(&mut self, i: &mut Macro) {
+        let self_ident = Ident::new("self", self.0.span());
+        i.tokens = replace_tokens(take(&mut i.tokens), &self_ident, self.0);
+    }
+
+    fn visit_item_mut(&mut self, _i: &mut Item) {
+        // Nested items cannot refer to our `self`.
+    }
+}
+
+// Qualifies associated types of the type param `ident` (usually `Self`) with the given trait, as
+// they become ambiguous when the param is replaced with another type, e.g. the implementing type.
+pub struct QualifyTypeParamPaths<'a> {
+    pub ident: Ident,
+    pub trait_path: &'a Path,
+}
+
+impl VisitMut for QualifyTypeParamPaths<'_> {
+    fn visit_type_path_mut(&mut self, i: &mut TypePath) {
+        if is_type_param_path(i, &self.ident) {
+            let first = i.path.segments.first().unwrap();
+            let mut segments = self.trait_path.segments.clone();
+            let position = segments.len();
+            segments.extend(i.path.segments.iter().skip(1).cloned());
+            i.qself = Some(QSelf {
+                lt_token: Default::default(),
+                ty: Box::new(Type::Path(TypePath {
+                    qself: None,
+                    path: first.ident.clone().into(),
+                })),
+                position,
+                as_token: Some(Default::default()),
+                gt_token: Default::default(),
+            });
+            i.path = Path {
+                leading_colon: self.trait_path.leading_colon,
+                segments,
+            };
+        }
+        visit_type_path_mut(self, i)
+    }
+
+    fn visit_item_mut(&mut self, _i: &mut Item) {
+        // Nested items cannot refer to our param.
+    }
+}
+
+// Finds paths that would be qualified by `QualifyTypeParamPaths`.
+pub struct FindTypeParamPaths<'a> {
+    pub ident: &'a Ident,
+    pub found: bool,
+}
+
+impl VisitMut for FindTypeParamPaths<'_> {
+    fn visit_type_path_mut(&mut self, i: &mut TypePath) {
+        if is_type_param_path(i, self.ident) {
+            self.found = true;
+        }
+        visit_type_path_mut(self, i)
+    }
+
+    fn visit_item_mut(&mut self, _i: &mut Item) {
+        // Nested items cannot refer to our param.
+    }
+}
+
+// Checks whether `path` refers to an associated item of the type param `ident`, as in `T::Item`.
+fn is_type_param_path(path: &TypePath, ident: &Ident) -> bool {
+    if path.qself.is_some() || path.path.leading_colon.is_some() || path.path.segments.len() < 2 {
+        return false;
+    }
+    let first = path.path.segments.first().unwrap();
+    &first.ident == ident && first.arguments.is_none()
+}
+
 pub struct MetaGenericArguments {
     pub lt_token: Option<Token![<]>,
     pub args: Punctuated<MetaGenericArgument, Token![,]>,
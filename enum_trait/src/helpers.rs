@@ -42,6 +42,40 @@ fn ident_span(ident: &Ident, preserve_span: bool) -> Span {
     }
 }
 
+// Replaces all parameter patterns except plain identifiers with generated identifiers, and removes
+// `mut` from `mut self` receivers, so that the parameters can be forwarded to another function,
+// and the signature can be used in a trait item without a body.
+pub fn name_param_patterns(sig: &mut Signature) {
+    for (arg_idx, arg) in sig.inputs.iter_mut().enumerate() {
+        let arg = match arg {
+            FnArg::Receiver(receiver) => {
+                if receiver.reference.is_none() {
+                    receiver.mutability = None;
+                }
+                continue;
+            }
+            FnArg::Typed(arg) => arg,
+        };
+        if !matches!(
+            arg.pat.as_ref(),
+            Pat::Ident(PatIdent {
+                by_ref: None,
+                mutability: None,
+                subpat: None,
+                ..
+            })
+        ) {
+            *arg.pat = Pat::Ident(PatIdent {
+                attrs: Vec::new(),
+                by_ref: None,
+                mutability: None,
+                ident: Ident::new(&format!("__arg{arg_idx}"), arg.pat.span()),
+                subpat: None,
+            });
+        }
+    }
+}
+
 pub fn get_type_ident(ty: &Type) -> Option<&Ident> {
     let Type::Path(TypePath { qself: None, path }) = ty else {
         return None;
@@ -60,6 +94,45 @@ where
     path.is_ident(ident)
 }
 
+// Checks whether a generic argument refers to the const param `ident`, either as `N` (which is
+// parsed as a type) or as `{ N }`.
+pub fn const_arg_is_ident(arg: &GenericArgument, ident: &Ident) -> bool {
+    match arg {
+        GenericArgument::Type(ty) => type_is_ident(ty, ident),
+        GenericArgument::Const(Expr::Block(ExprBlock {
+            attrs,
+            label: None,
+            block,
+            ..
+        })) if attrs.is_empty() => {
+            matches!(
+                block.stmts.as_slice(),
+                [Stmt::Expr(Expr::Path(ExprPath { qself: None, path, .. }), None)]
+                    if path.is_ident(ident)
+            )
+        }
+        _ => false,
+    }
+}
+
+pub fn trait_item_ident(item: &TraitItem) -> Option<&Ident> {
+    match item {
+        TraitItem::Const(const_item) => Some(&const_item.ident),
+        TraitItem::Fn(fn_item) => Some(&fn_item.sig.ident),
+        TraitItem::Type(type_item) => Some(&type_item.ident),
+        _ => None,
+    }
+}
+
+pub fn impl_item_ident(item: &ImplItem) -> Option<&Ident> {
+    match item {
+        ImplItem::Const(const_item) => Some(&const_item.ident),
+        ImplItem::Fn(fn_item) => Some(&fn_item.sig.ident),
+        ImplItem::Type(type_item) => Some(&type_item.ident),
+        _ => None,
+    }
+}
+
 pub fn build_generics(params: Punctuated<GenericParam, Token![,]>) -> Generics {
     let (lt_token, gt_token) = if params.is_empty() {
         (None, None)
@@ -184,6 +257,26 @@ pub fn check_token_equality<T: ToTokens>(actual: &T, expected: &T) -> Result<()>
     Ok(())
 }
 
+pub fn add_error(errors: &mut Option<Error>, error: Error) {
+    if let Some(errors) = errors {
+        errors.combine(error);
+    } else {
+        *errors = Some(error);
+    }
+}
+
+// Converts tokens to a string for use in error messages, omitting most unnecessary spaces.
+pub fn tokens_to_string(tokens: TokenStream) -> String {
+    tokens
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(" : ", ": ")
+        .replace(" ,", ",")
+        .replace("< ", "<")
+        .replace(" <", "<")
+        .replace(" >", ">")
+}
+
 fn is_apostrophe_token(token: &Option<TokenTree>) -> bool {
     if let Some(TokenTree::Punct(punct)) = token {
         punct.as_char() == '\'' && punct.spacing() == Spacing::Joint
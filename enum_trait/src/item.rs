@@ -1,12 +1,18 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::{
-    parse::{Parse, ParseBuffer, ParseStream},
+    parse::{Parse, ParseBuffer, ParseStream, Parser},
     punctuated::Punctuated,
     spanned::Spanned,
     visit_mut::VisitMut,
     *,
 };
 
-use crate::{expr::*, generics::*, helpers::*, output::*, subst::*};
+use crate::{config::*, expr::*, generics::*, helpers::*, output::*, subst::*};
+
+mod kw {
+    syn::custom_keyword!(extend);
+}
 
 pub struct MetaItemList(pub Vec<MetaItem>);
 
@@ -21,6 +27,47 @@ impl Parse for MetaItemList {
 }
 
 impl MetaItemList {
+    // Finds the type aliases in the block that declare a trait as their bound, before the items
+    // are parsed, so that `let` bindings can refer to aliases defined anywhere in the block.
+    // Since type aliases are the only items starting with `type`, we just try to parse their
+    // beginning at each top-level occurrence of the keyword. Errors are reported when parsing the
+    // actual items.
+    pub fn declared_type_traits(input: ParseStream) -> Vec<(Ident, Path)> {
+        let parse_type_trait = |input: ParseStream| {
+            input.parse::<Token![type]>()?;
+            let ident: Ident = input.parse()?;
+            input.parse::<MetaGenerics>()?;
+            let mut trait_path = None;
+            if input.parse::<Option<Token![:]>>()?.is_some() {
+                trait_path = parse_type_param_bounds(input)?
+                    .into_iter()
+                    .find_map(|bound| match bound {
+                        TypeParamBound::Trait(trait_bound) => Some(trait_bound.path),
+                        _ => None,
+                    })
+                    // Arguments of the trait may refer to parameters of the alias.
+                    .filter(|path| {
+                        path.segments
+                            .iter()
+                            .all(|segment| segment.arguments.is_none())
+                    });
+            }
+            input.parse::<TokenStream>()?;
+            Ok(trait_path.map(|trait_path| (ident, trait_path)))
+        };
+        let mut type_traits = Vec::new();
+        let mut cursor = input.cursor();
+        while let Some((token, next)) = cursor.token_tree() {
+            if matches!(&token, TokenTree::Ident(ident) if ident == "type") {
+                if let Ok(Some(type_trait)) = parse_type_trait.parse2(cursor.token_stream()) {
+                    type_traits.push(type_trait);
+                }
+            }
+            cursor = next;
+        }
+        type_traits
+    }
+
     pub fn output(&self) -> Result<OutputMetaItemList> {
         let mut result = OutputMetaItemList::new();
 
@@ -29,30 +76,15 @@ impl MetaItemList {
             if let MetaItem::TraitDef(trait_def) = item {
                 let extracted_generics = trait_def.generics.extract_generics();
                 let mut trait_variants = None;
+                let mut variant_order = None;
                 let mut dependent_idents = Vec::new();
+                let mut init_variants = None;
                 match &trait_def.contents {
                     TraitContents::Enum { variants } => {
-                        let mut trait_generics = extracted_generics.clone();
-                        add_underscores_to_all_params(&mut trait_generics)?;
-                        trait_variants = Some(
-                            variants
-                                .iter()
-                                .map(|variant| {
-                                    let mut variant = variant.clone();
-                                    add_underscores_to_all_params(&mut variant.generics)?;
-                                    Ok(OutputImplVariant {
-                                        variant: ImplVariant {
-                                            impl_generics: trait_generics.clone(),
-                                            trait_args: generic_args(&trait_generics),
-                                            variant,
-                                        },
-                                        impl_items: ImplPartList::new(),
-                                    })
-                                })
-                                .collect::<Result<_>>()?,
-                        )
+                        init_variants = Some(variants.iter().cloned().collect());
                     }
                     TraitContents::Alias { path } => {
+                        Self::check_alias_lifetime_args(trait_def, &extracted_generics, path)?;
                         for arg in &path.arguments.args {
                             if let MetaGenericArgument::TraitAlias(alias_arg) = arg {
                                 trait_def.add_path_to_dependencies(
@@ -61,15 +93,53 @@ impl MetaItemList {
                                 );
                             }
                         }
+                        if let Some((variants, exact)) = self.aliased_trait_variants(trait_def) {
+                            variant_order = Some(
+                                variants
+                                    .iter()
+                                    .map(|variant| variant.ident.clone())
+                                    .collect(),
+                            );
+                            if exact {
+                                init_variants = Some(variants);
+                            }
+                        }
                     }
                 }
+                if let Some(variants) = init_variants {
+                    let mut trait_generics = extracted_generics.clone();
+                    add_underscores_to_non_lifetime_params(&mut trait_generics)?;
+                    trait_variants = Some(
+                        variants
+                            .into_iter()
+                            .map(|mut variant| {
+                                // Variant params may refer to trait params, e.g. lifetimes.
+                                variant
+                                    .generics
+                                    .substitute_all_params(&extracted_generics, &trait_generics)?;
+                                add_underscores_to_all_params(&mut variant.generics)?;
+                                Ok(OutputImplVariant {
+                                    variant: ImplVariant {
+                                        impl_generics: trait_generics.clone(),
+                                        trait_args: generic_args(&trait_generics),
+                                        variant,
+                                    },
+                                    impl_items: ImplPartList::new(),
+                                })
+                            })
+                            .collect::<Result<_>>()?,
+                    )
+                }
                 result.0.push(OutputMetaItem::TraitDef(OutputItemTraitDef {
                     trait_def,
                     extracted_generics,
                     variants: trait_variants,
+                    variant_order,
                     impl_items: ImplPartList::new(),
                     dependent_idents,
                     next_internal_item_idx: 0,
+                    variant_defaults: Vec::new(),
+                    warnings: Vec::new(),
                 }));
             }
         }
@@ -91,18 +161,49 @@ impl MetaItemList {
                     check_token_equality(&impl_item.generics, &trait_def.generics)?;
                     Self::check_trait_impl_args(&impl_item.generics, &segment.arguments)?;
                     let impl_context = trait_def_item.impl_context();
-                    let variants_known = trait_def_item.variants.is_some();
+                    if let Some((_, foreign_trait)) = &impl_item.foreign_trait {
+                        let mut items = Vec::new();
+                        for item in &impl_item.items {
+                            items.push(Self::output_impl_item(
+                                &mut result,
+                                item,
+                                &Type::Path(TypePath {
+                                    qself: None,
+                                    path: self_type_ident(None).into(),
+                                }),
+                                Some(foreign_trait),
+                                &impl_context,
+                            )?);
+                        }
+                        Self::output_foreign_trait_impls(
+                            &mut result,
+                            trait_def,
+                            foreign_trait,
+                            items,
+                        )?;
+                        continue;
+                    }
+                    // Report errors in variants of all items at once.
+                    let mut errors = None;
                     for item in &impl_item.items {
+                        // Previous items may have determined the variants.
+                        let known_variants =
+                            result.trait_def_item(&segment.ident)?.known_variants();
                         let mut part_ident = None;
                         let trait_item_desc = result.create_trait_item(
                             &mut part_ident,
                             item.clone(),
                             &impl_context,
                             trait_def,
-                            variants_known,
+                            known_variants.as_deref(),
                         )?;
                         let trait_def_item = result.trait_def_item(&segment.ident)?;
-                        trait_def_item.add_item(&part_ident, trait_item_desc)?;
+                        if let Err(error) = trait_def_item.add_item(&part_ident, trait_item_desc) {
+                            add_error(&mut errors, error);
+                        }
+                    }
+                    if let Some(errors) = errors {
+                        return Err(errors);
                     }
                 }
 
@@ -136,19 +237,118 @@ impl MetaItemList {
                     })));
                 }
 
+                MetaItem::Impl(impl_item) => {
+                    let impl_context =
+                        GenericsContext::WithGenerics(&impl_item.generics, &GenericsContext::Empty);
+                    let mut items = Vec::new();
+                    for item in &impl_item.items {
+                        if let (None, TraitImplItem::Type(type_item)) = (&impl_item.trait_, item) {
+                            return Err(Error::new(
+                                type_item.ident.span(),
+                                "associated types are not supported in inherent impls; use a generic type alias instead",
+                            ));
+                        }
+                        items.push(Self::output_impl_item(
+                            &mut result,
+                            item,
+                            &impl_item.self_ty,
+                            impl_item.trait_.as_ref().map(|(path, _)| path),
+                            &impl_context,
+                        )?);
+                    }
+                    result.0.push(OutputMetaItem::Item(Item::Impl(ItemImpl {
+                        attrs: impl_item.attrs.clone(),
+                        defaultness: None,
+                        unsafety: None,
+                        impl_token: impl_item.impl_token,
+                        generics: impl_item.generics.clone(),
+                        trait_: impl_item
+                            .trait_
+                            .clone()
+                            .map(|(path, for_token)| (None, path, for_token)),
+                        self_ty: Box::new(impl_item.self_ty.clone()),
+                        brace_token: Default::default(),
+                        items,
+                    })));
+                }
+
+                MetaItem::Struct(struct_item) => {
+                    Self::check_derives(&struct_item.attrs, [&struct_item.fields])?;
+                    let context = GenericsContext::WithGenerics(
+                        &struct_item.generics,
+                        &GenericsContext::Empty,
+                    );
+                    let fields = Self::output_fields(
+                        &mut result,
+                        &struct_item.fields,
+                        &struct_item.ident,
+                        &context,
+                    )?;
+                    result.0.push(OutputMetaItem::Item(Item::Struct(ItemStruct {
+                        attrs: OutputMetaItemList::code_item_attrs(struct_item.attrs.clone()),
+                        vis: struct_item.vis.clone(),
+                        struct_token: struct_item.struct_token,
+                        ident: struct_item.ident.clone(),
+                        generics: struct_item.generics.clone(),
+                        fields,
+                        semi_token: struct_item.semi_token,
+                    })));
+                }
+
+                MetaItem::Enum(enum_item) => {
+                    Self::check_derives(
+                        &enum_item.attrs,
+                        enum_item.variants.iter().map(|variant| &variant.fields),
+                    )?;
+                    let context =
+                        GenericsContext::WithGenerics(&enum_item.generics, &GenericsContext::Empty);
+                    let mut variants = Punctuated::new();
+                    for variant in &enum_item.variants {
+                        variants.push(Variant {
+                            attrs: variant.attrs.clone(),
+                            ident: variant.ident.clone(),
+                            fields: Self::output_fields(
+                                &mut result,
+                                &variant.fields,
+                                &enum_item.ident,
+                                &context,
+                            )?,
+                            discriminant: variant.discriminant.clone(),
+                        });
+                    }
+                    result.0.push(OutputMetaItem::Item(Item::Enum(ItemEnum {
+                        attrs: OutputMetaItemList::code_item_attrs(enum_item.attrs.clone()),
+                        vis: enum_item.vis.clone(),
+                        enum_token: enum_item.enum_token,
+                        ident: enum_item.ident.clone(),
+                        generics: enum_item.generics.clone(),
+                        brace_token: Default::default(),
+                        variants,
+                    })));
+                }
+
                 MetaItem::Fn(fn_item) => {
-                    let extracted_sig = fn_item.sig.extract_signature();
+                    let mut extracted_sig = fn_item.sig.extract_signature();
+                    let part_ident = Some(fn_item.sig.ident.clone());
+                    result.lift_inline_matches_in_signature(
+                        &part_ident,
+                        &mut extracted_sig,
+                        &GenericsContext::Empty,
+                    )?;
                     let context = GenericsContext::WithGenerics(
                         &extracted_sig.generics,
                         &GenericsContext::Empty,
                     );
                     let block = result.convert_type_level_expr_fn(
                         &fn_item.attrs,
-                        &Some(fn_item.sig.ident.clone()),
+                        &part_ident,
                         fn_item.block.clone(),
                         &context,
                         &extracted_sig,
                     )?;
+                    if matches!(fn_item.block, TypeLevelExpr::Match(_)) {
+                        name_param_patterns(&mut extracted_sig);
+                    }
                     result.0.push(OutputMetaItem::Item(Item::Fn(ItemFn {
                         attrs: OutputMetaItemList::code_item_attrs(fn_item.attrs.clone()),
                         vis: fn_item.vis.clone(),
@@ -159,8 +359,41 @@ impl MetaItemList {
             }
         }
 
+        // The variants of aliases of traits whose variants are determined by their `match`
+        // expressions must be ordered in the same way as those of the aliased trait.
+        let variant_orders: Vec<(Ident, Vec<Ident>)> = result
+            .0
+            .iter()
+            .filter_map(|item| match item {
+                OutputMetaItem::TraitDef(trait_def_item) => Some((
+                    trait_def_item.trait_def.ident.clone(),
+                    trait_def_item
+                        .known_variants()?
+                        .into_iter()
+                        .map(|variant| variant.ident)
+                        .collect(),
+                )),
+                _ => None,
+            })
+            .collect();
+
         for item in &mut result.0 {
             if let OutputMetaItem::TraitDef(trait_def_item) = item {
+                if let (Some(target), Some(variants)) = (
+                    self.forwarding_target(trait_def_item.trait_def),
+                    &mut trait_def_item.variants,
+                ) {
+                    if let Some((_, order)) = variant_orders
+                        .iter()
+                        .find(|(ident, _)| ident == &target.ident)
+                    {
+                        variants.sort_by_key(|variant| {
+                            order
+                                .iter()
+                                .position(|ident| ident == &variant.variant.variant.ident)
+                        });
+                    }
+                }
                 if let Some(variants) = &mut trait_def_item.variants {
                     for variant in variants {
                         RemoveTypeBoundParamsFromPathArguments(&trait_def_item.trait_def.generics)
@@ -189,6 +422,420 @@ impl MetaItemList {
         Ok(result)
     }
 
+    // Checks whether `trait_def` is an alias that does not have independent impls, but forwards
+    // its variants to the implementation macro of the aliased trait.
+    fn is_forwarding_alias(trait_def: &ItemTraitDef) -> bool {
+        match &trait_def.contents {
+            TraitContents::Enum { .. } => false,
+            TraitContents::Alias { path } => {
+                trait_def.generics.where_clause.is_none() && !path.arguments.has_complex_type_arg()
+            }
+        }
+    }
+
+    fn local_trait_def(&self, path: &TraitPath) -> Option<&ItemTraitDef> {
+        if path.leading_colon.is_some() || path.segments.len() != 1 {
+            return None;
+        }
+        let ident = path.segments.first().unwrap();
+        self.0.iter().find_map(|item| match item {
+            MetaItem::TraitDef(trait_def)
+                if &trait_def.ident == ident && trait_def.extended_trait.is_none() =>
+            {
+                Some(trait_def)
+            }
+            _ => None,
+        })
+    }
+
+    // Follows a chain of forwarding aliases within the block, and returns the trait whose
+    // implementation macro is eventually invoked, if it is defined in the block.
+    fn forwarding_target<'b>(&'b self, trait_def: &'b ItemTraitDef) -> Option<&'b ItemTraitDef> {
+        let mut target = trait_def;
+        // Cyclic aliases are reported by the compiler, so we just need to terminate.
+        for _ in 0..self.0.len() {
+            if !Self::is_forwarding_alias(target) {
+                return (target.ident != trait_def.ident).then_some(target);
+            }
+            let TraitContents::Alias { path } = &target.contents else {
+                unreachable!()
+            };
+            target = self.local_trait_def(path)?;
+        }
+        None
+    }
+
+    // For a forwarding alias, determines the variants of the aliased trait in the order in which
+    // its implementation macro expects them, if they are known before processing any `match`
+    // expressions. Bounds referring to the aliased trait are replaced with the alias. The second
+    // element specifies whether the variants are also valid as variants of the alias, which is
+    // not the case if the alias or the aliased trait is generic.
+    fn aliased_trait_variants(
+        &self,
+        trait_def: &ItemTraitDef,
+    ) -> Option<(Vec<TraitVariant>, bool)> {
+        let mut exact = true;
+        let mut target = trait_def;
+        for _ in 0..self.0.len() {
+            let variants: Vec<TraitVariant> = match &target.contents {
+                TraitContents::Enum { variants } => variants.iter().cloned().collect(),
+                TraitContents::Alias { path } => {
+                    if !Self::is_forwarding_alias(target) {
+                        return None;
+                    }
+                    if !target.generics.params.is_empty() || path.arguments.lt_token.is_some() {
+                        exact = false;
+                    }
+                    if let Some(local_target) = self.local_trait_def(path) {
+                        target = local_target;
+                        continue;
+                    }
+                    BlockConfig::with_current(|config| {
+                        Some(config.aliased_variants(&target.ident)??.to_vec())
+                    })?
+                }
+            };
+            return variants
+                .into_iter()
+                .map(|variant| {
+                    let generics = replace_tokens(
+                        variant.generics.to_token_stream(),
+                        &target.ident,
+                        &trait_def.ident,
+                    );
+                    Ok(TraitVariant {
+                        attrs: Vec::new(),
+                        ident: variant.ident,
+                        generics: parse2(generics)?,
+                    })
+                })
+                .collect::<Result<_>>()
+                .ok()
+                .map(|variants| (variants, exact));
+        }
+        None
+    }
+
+    // Returns the alias of a trait defined outside of the block whose variants we need to know,
+    // together with the path of the macro that passes them to the block.
+    pub fn variants_request(&self) -> Option<(&Ident, Path)> {
+        self.0.iter().find_map(|item| {
+            let MetaItem::TraitDef(trait_def) = item else {
+                return None;
+            };
+            let TraitContents::Alias { path } = &trait_def.contents else {
+                return None;
+            };
+            if !Self::is_forwarding_alias(trait_def)
+                || self.local_trait_def(path).is_some()
+                || BlockConfig::with_current(|config| {
+                    config.aliased_variants(&trait_def.ident).is_some()
+                })
+            {
+                return None;
+            }
+            let mut macro_path = path.extract_path();
+            let segment = macro_path.segments.last_mut().unwrap();
+            segment.ident = OutputItemTraitDef::variants_macro_ident(&segment.ident);
+            segment.arguments = PathArguments::None;
+            Some((&trait_def.ident, macro_path))
+        })
+    }
+
+    fn output_impl_item(
+        result: &mut OutputMetaItemList,
+        item: &TraitImplItem,
+        self_ty: &Type,
+        trait_path: Option<&Path>,
+        impl_context: &GenericsContext,
+    ) -> Result<ImplItem> {
+        // Match arms are moved into impls of other types, so `Self` must be replaced with the
+        // type of the impl block.
+        let self_param = self_type_param(None, Punctuated::new());
+        let self_arg = GenericArgument::Type(self_ty.clone());
+        let self_subst = ParamSubstArg::Arg(&self_arg);
+        let mut qualify = trait_path.map(|trait_path| QualifyTypeParamPaths {
+            ident: self_type_ident(None),
+            trait_path,
+        });
+        match item {
+            TraitImplItem::Type(type_item) => {
+                let mut expr = type_item.ty.clone();
+                if let TypeLevelExpr::Match(match_expr) = &mut expr {
+                    if let Some(qualify) = &mut qualify {
+                        match_expr.visit_arm_bodies_mut(&mut |ty| qualify.visit_type_mut(ty));
+                    }
+                    match_expr.substitute(&self_param, self_subst)?;
+                }
+                let context = GenericsContext::WithGenerics(&type_item.generics, impl_context);
+                let ty = result.convert_type_level_expr_type(
+                    &type_item.attrs,
+                    &Some(type_item.ident.clone()),
+                    expr,
+                    &context,
+                    &type_item.bounds,
+                )?;
+                Ok(ImplItem::Type(ImplItemType {
+                    attrs: OutputMetaItemList::code_item_attrs(type_item.attrs.clone()),
+                    vis: type_item.vis.clone(),
+                    defaultness: None,
+                    type_token: type_item.type_token,
+                    ident: type_item.ident.clone(),
+                    generics: type_item.generics.clone(),
+                    eq_token: Default::default(),
+                    ty,
+                    semi_token: Default::default(),
+                }))
+            }
+
+            TraitImplItem::Const(const_item) => {
+                let mut expr = const_item.expr.clone();
+                let mut ty = const_item.ty.clone();
+                if let TypeLevelExpr::Match(match_expr) = &mut expr {
+                    if let Some(qualify) = &mut qualify {
+                        match_expr.visit_arm_bodies_mut(&mut |expr| qualify.visit_expr_mut(expr));
+                        qualify.visit_type_mut(&mut ty);
+                    }
+                    match_expr.substitute(&self_param, self_subst)?;
+                    ty.substitute(&self_param, self_subst)?;
+                }
+                let expr = result.convert_type_level_expr_const(
+                    &const_item.attrs,
+                    &Some(const_item.ident.clone()),
+                    expr,
+                    impl_context,
+                    &ty,
+                )?;
+                Ok(ImplItem::Const(ImplItemConst {
+                    attrs: OutputMetaItemList::code_item_attrs(const_item.attrs.clone()),
+                    vis: const_item.vis.clone(),
+                    defaultness: None,
+                    const_token: const_item.const_token,
+                    ident: const_item.ident.clone(),
+                    generics: Generics::default(),
+                    colon_token: Default::default(),
+                    ty: const_item.ty.clone(),
+                    eq_token: Default::default(),
+                    expr,
+                    semi_token: Default::default(),
+                }))
+            }
+
+            TraitImplItem::Fn(fn_item) => {
+                let mut impl_sig = fn_item.sig.clone();
+                let part_ident = Some(fn_item.sig.ident.clone());
+                result.lift_inline_matches_in_signature(
+                    &part_ident,
+                    &mut impl_sig,
+                    impl_context,
+                )?;
+                let mut expr = fn_item.block.clone();
+                let mut sig = impl_sig.clone();
+                if let TypeLevelExpr::Match(match_expr) = &mut expr {
+                    if let Some(qualify) = &mut qualify {
+                        match_expr.visit_arm_bodies_mut(&mut |expr| qualify.visit_expr_mut(expr));
+                        qualify.visit_signature_mut(&mut sig);
+                    }
+                    match_expr.substitute(&self_param, self_subst)?;
+                    sig.substitute(&self_param, self_subst)?;
+                }
+                let context = GenericsContext::WithGenerics(&sig.generics, impl_context);
+                let block = result.convert_type_level_expr_fn(
+                    &fn_item.attrs,
+                    &part_ident,
+                    expr,
+                    &context,
+                    &sig,
+                )?;
+                let mut sig = impl_sig;
+                if matches!(fn_item.block, TypeLevelExpr::Match(_)) {
+                    name_param_patterns(&mut sig);
+                }
+                Ok(ImplItem::Fn(ImplItemFn {
+                    attrs: OutputMetaItemList::code_item_attrs(fn_item.attrs.clone()),
+                    vis: fn_item.vis.clone(),
+                    defaultness: None,
+                    sig,
+                    block,
+                }))
+            }
+        }
+    }
+
+    // Implements a trait that is not defined in the block for all variants of an enum trait, by
+    // implementing it for each variant type separately. The items have already been converted
+    // with `Self` referring to the variant type, so all impls share them.
+    fn output_foreign_trait_impls(
+        result: &mut OutputMetaItemList,
+        trait_def: &ItemTraitDef,
+        foreign_trait: &Path,
+        items: Vec<ImplItem>,
+    ) -> Result<()> {
+        let attrs = OutputMetaItemList::code_item_attrs(Vec::new());
+        // Only lifetime params of the trait can be threaded through, as type and const params would
+        // not be constrained by the impls.
+        let trait_generics = trait_def.generics.extract_generics();
+        if let Some(param) = trait_generics
+            .params
+            .iter()
+            .find(|param| !matches!(param, GenericParam::Lifetime(_)))
+        {
+            return Err(Error::new(
+                param.span(),
+                "implementing other traits for variants is only supported if all type and const parameters of the enum trait are trait bound parameters",
+            ));
+        }
+        match &trait_def.contents {
+            TraitContents::Enum { variants } => {
+                for variant in variants {
+                    let variant_ident = &variant.ident;
+                    let mut variant_generics = variant.generics.clone();
+                    RemoveTypeBoundParamsFromPathArguments(&trait_def.generics)
+                        .visit_generics_mut(&mut variant_generics);
+                    trait_def.generics.erase_in_generics(&mut variant_generics);
+                    let variant_args = generic_args(&variant_generics);
+                    let mut impl_generics = trait_generics.clone();
+                    impl_generics.params.extend(variant_generics.params);
+                    if let Some(where_clause) = variant_generics.where_clause {
+                        impl_generics
+                            .make_where_clause()
+                            .predicates
+                            .extend(where_clause.predicates);
+                    }
+                    let where_clause = &impl_generics.where_clause;
+                    result.0.push(OutputMetaItem::Item(parse_quote! {
+                        #(#attrs)*
+                        impl #impl_generics #foreign_trait for #variant_ident #variant_args
+                            #where_clause
+                        {
+                            #(#items)*
+                        }
+                    }));
+                }
+            }
+            TraitContents::Alias { .. } => {
+                // The variants of externally defined traits are not known here, so we output a
+                // blanket impl, which is only possible if the implemented trait is local.
+                let trait_ident = &trait_def.ident;
+                let trait_args = generic_args(&trait_generics);
+                let mut impl_generics = trait_generics.clone();
+                impl_generics
+                    .params
+                    .push(parse_quote!(__Self: #trait_ident #trait_args));
+                let where_clause = &impl_generics.where_clause;
+                result.0.push(OutputMetaItem::Item(parse_quote! {
+                    #(#attrs)*
+                    impl #impl_generics #foreign_trait for __Self #where_clause {
+                        #(#items)*
+                    }
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    // The types of fields with type-level matches are converted to associated types. Derived impls
+    // only require the derived trait for type parameters, not for such field types, and the
+    // corresponding bounds cannot be added to the type itself because they would then need to be
+    // satisfied wherever it is used. The traits must be implemented manually instead,
+    // e.g. with a type-level `match` in the function bodies.
+    fn check_derives<'b>(
+        attrs: &[Attribute],
+        fields: impl IntoIterator<Item = &'b FieldsExt>,
+    ) -> Result<()> {
+        let Some(derive_attr) = attrs.iter().find(|attr| attr.path().is_ident("derive")) else {
+            return Ok(());
+        };
+        let has_match_field = fields.into_iter().any(|fields| match fields {
+            FieldsExt::Named(fields) | FieldsExt::Unnamed(fields) => {
+                fields.iter().any(|field| match &field.ty {
+                    TypeLevelExpr::Expr(ty) => contains_inline_match(ty.to_token_stream()),
+                    TypeLevelExpr::Match(_) => true,
+                })
+            }
+            FieldsExt::Unit => false,
+        });
+        if has_match_field {
+            return Err(Error::new_spanned(
+                derive_attr,
+                "`#[derive]` is not supported for types with type-level `match` fields; implement the traits manually instead",
+            ));
+        }
+        Ok(())
+    }
+
+    fn output_fields(
+        result: &mut OutputMetaItemList,
+        fields: &FieldsExt,
+        item_ident: &Ident,
+        context: &GenericsContext,
+    ) -> Result<Fields> {
+        let mut output_fields = Punctuated::new();
+        let fields_ext = match fields {
+            FieldsExt::Named(fields_ext) | FieldsExt::Unnamed(fields_ext) => fields_ext,
+            FieldsExt::Unit => return Ok(Fields::Unit),
+        };
+        for field in fields_ext {
+            let ty = result.convert_type_level_expr_type(
+                &field.attrs,
+                &Some(item_ident.clone()),
+                field.ty.clone(),
+                context,
+                &Punctuated::new(),
+            )?;
+            output_fields.push(Field {
+                attrs: field.attrs.clone(),
+                vis: field.vis.clone(),
+                mutability: FieldMutability::None,
+                ident: field.ident.clone(),
+                colon_token: field.colon_token,
+                ty,
+            });
+        }
+        if let FieldsExt::Named(_) = fields {
+            Ok(Fields::Named(FieldsNamed {
+                brace_token: Default::default(),
+                named: output_fields,
+            }))
+        } else {
+            Ok(Fields::Unnamed(FieldsUnnamed {
+                paren_token: Default::default(),
+                unnamed: output_fields,
+            }))
+        }
+    }
+
+    // Unless an alias has independent impls, its lifetime params are passed on to the macros of
+    // the aliased trait in the same way as type bound params, so the lifetime args of the aliased
+    // trait must coincide with them.
+    fn check_alias_lifetime_args(
+        trait_def: &ItemTraitDef,
+        alias_generics: &Generics,
+        path: &TraitPath,
+    ) -> Result<()> {
+        let mut lifetime_params = alias_generics.lifetimes();
+        for arg in &path.arguments.args {
+            if let MetaGenericArgument::Generic(GenericArgument::Lifetime(lifetime)) = arg {
+                let lifetime_param = lifetime_params.next();
+                if !matches!(lifetime_param, Some(param) if &param.lifetime == lifetime) {
+                    return Err(Error::new_spanned(
+                        lifetime,
+                        "lifetime arguments of aliased trait must be the lifetime parameters of the alias, in the same order",
+                    ));
+                }
+            }
+        }
+        let independent_impls =
+            trait_def.generics.where_clause.is_some() || path.arguments.has_complex_type_arg();
+        if let Some(lifetime_param) = lifetime_params.next().filter(|_| !independent_impls) {
+            return Err(Error::new_spanned(
+                lifetime_param,
+                "lifetime parameter of alias must be passed to aliased trait",
+            ));
+        }
+        Ok(())
+    }
+
     fn check_trait_impl_args(
         impl_item_generics: &MetaGenerics,
         impl_item_args: &PathArguments,
@@ -236,11 +883,8 @@ impl MetaItemList {
                             }
                         }
                         GenericParam::Const(const_param) => {
-                            let GenericArgument::Type(arg_ty) = arg else {
-                                return Err(Error::new_spanned(arg, "variable expected"));
-                            };
                             let const_ident = &const_param.ident;
-                            if !type_is_ident(arg_ty, const_ident) {
+                            if !const_arg_is_ident(arg, const_ident) {
                                 return Err(Error::new_spanned(
                                     arg,
                                     format!("constant `{const_ident}` expected"),
@@ -270,6 +914,9 @@ impl MetaItemList {
 pub enum MetaItem {
     TraitDef(ItemTraitDef),
     TraitImpl(ItemTraitImpl),
+    Impl(ItemImplExt),
+    Struct(ItemStructExt),
+    Enum(ItemEnumExt),
     Type(ItemTypeExt),
     Fn(ItemFnExt),
 }
@@ -289,7 +936,9 @@ impl Parse for MetaItem {
             lookahead = ahead.lookahead1();
         }
 
-        if !reuse && lookahead.peek(Token![trait]) {
+        if !reuse && ahead.peek(kw::extend) && ahead.peek2(Token![enum]) {
+            return Ok(MetaItem::TraitDef(ItemTraitDef::parse(input, attrs)?));
+        } else if !reuse && lookahead.peek(Token![trait]) {
             ahead.parse::<Token![trait]>()?;
             lookahead = ahead.lookahead1();
             if lookahead.peek(Token![impl]) {
@@ -298,7 +947,16 @@ impl Parse for MetaItem {
                 return Ok(MetaItem::TraitDef(ItemTraitDef::parse(input, attrs)?));
             }
         } else if !reuse && lookahead.peek(Token![enum]) {
-            return Ok(MetaItem::TraitDef(ItemTraitDef::parse(input, attrs)?));
+            ahead.parse::<Token![enum]>()?;
+            if ahead.peek(Token![trait]) {
+                return Ok(MetaItem::TraitDef(ItemTraitDef::parse(input, attrs)?));
+            } else {
+                return Ok(MetaItem::Enum(ItemEnumExt::parse(input, attrs)?));
+            }
+        } else if !reuse && lookahead.peek(Token![struct]) {
+            return Ok(MetaItem::Struct(ItemStructExt::parse(input, attrs)?));
+        } else if !reuse && lookahead.peek(Token![impl]) {
+            return Ok(MetaItem::Impl(ItemImplExt::parse(input, attrs)?));
         } else if lookahead.peek(Token![type]) {
             return Ok(MetaItem::Type(ItemTypeExt::parse(input, attrs)?));
         } else if lookahead.peek(Token![const]) {
@@ -321,17 +979,68 @@ pub struct ItemTraitDef {
     pub ident: Ident,
     pub generics: MetaGenerics,
     pub contents: TraitContents,
+    // Enum traits are sealed unless marked with `#[open]`.
+    pub open: bool,
+    // For `extend enum trait`, the path of the (open) enum trait that the variants are added to.
+    // Its last segment is `ident`.
+    pub extended_trait: Option<Path>,
 }
 
 impl ItemTraitDef {
     fn parse(input: ParseStream, attrs: Vec<Attribute>) -> Result<Self> {
+        let mut attrs = attrs;
+        let mut open_attr = None;
+        attrs.retain(|attr| {
+            if attr.path().is_ident("open") {
+                open_attr = Some(attr.clone());
+                false
+            } else {
+                true
+            }
+        });
+        if let Some(Attribute {
+            meta: Meta::List(_) | Meta::NameValue(_),
+            ..
+        }) = &open_attr
+        {
+            return Err(Error::new_spanned(
+                open_attr,
+                "`#[open]` does not take arguments",
+            ));
+        }
         let vis: Visibility = input.parse()?;
+        let extend_token = input.parse::<Option<kw::extend>>()?;
         let enum_token = input.parse::<Option<Token![enum]>>()?;
+        if enum_token.is_none() || extend_token.is_some() {
+            if let Some(open_attr) = open_attr {
+                return Err(Error::new_spanned(
+                    open_attr,
+                    "`#[open]` is only supported on enum traits",
+                ));
+            }
+        }
         let trait_token: Token![trait] = input.parse()?;
-        let ident: Ident = input.parse()?;
+        let mut extended_trait = None;
+        let ident: Ident = if extend_token.is_some() {
+            let path = Path::parse_mod_style(input)?;
+            let ident = path.segments.last().unwrap().ident.clone();
+            extended_trait = Some(path);
+            ident
+        } else {
+            input.parse()?
+        };
         let mut generics: MetaGenerics = input.parse()?;
+        if extend_token.is_some() && !generics.params.is_empty() {
+            return Err(Error::new(
+                generics.params.span(),
+                "extending generic enum traits is not supported",
+            ));
+        }
         let contents = if enum_token.is_some() {
             if input.peek(Token![where]) {
+                if extend_token.is_some() {
+                    return Err(input.error("extending generic enum traits is not supported"));
+                }
                 generics.where_clause = Some(input.parse()?);
             };
             let content: ParseBuffer;
@@ -354,9 +1063,38 @@ impl ItemTraitDef {
             ident,
             generics,
             contents,
+            open: open_attr.is_some(),
+            extended_trait,
         })
     }
 
+    // In the arms of an alias, or of a `match` expression on a type bounded by an alias, variant
+    // params may also be bounded by the aliased trait, as in `Succ<P: MetaNum>` for an alias of
+    // `MetaNum`. The variants of the alias are implemented with params bounded by the alias, which
+    // implies the aliased trait, so we treat such bounds as referring to the alias.
+    pub fn replace_aliased_trait_bounds(&self, generics: &mut Generics) {
+        let TraitContents::Alias { path } = &self.contents else {
+            return;
+        };
+        // An alias with a `where` clause does not imply itself for the params.
+        if path.arguments.lt_token.is_some()
+            || !self.generics.params.is_empty()
+            || self.generics.where_clause.is_some()
+        {
+            return;
+        }
+        let aliased_trait = path.extract_path().to_token_stream().to_string();
+        for type_param in generics.type_params_mut() {
+            for bound in &mut type_param.bounds {
+                if let TypeParamBound::Trait(trait_bound) = bound {
+                    if trait_bound.path.to_token_stream().to_string() == aliased_trait {
+                        trait_bound.path = self.ident.clone().into();
+                    }
+                }
+            }
+        }
+    }
+
     pub fn collect_dependencies_in_generics(
         &self,
         generics: &Generics,
@@ -533,6 +1271,8 @@ impl Parse for TraitPath {
 pub struct ItemTraitImpl {
     pub generics: MetaGenerics,
     pub self_trait: Path,
+    // A trait that is not defined in the block, which is implemented for all variants.
+    pub foreign_trait: Option<(Token![for], Path)>,
     pub items: Vec<TraitImplItem>,
 }
 
@@ -548,6 +1288,11 @@ impl ItemTraitImpl {
         input.parse::<Token![impl]>()?;
         let generics: MetaGenerics = input.parse()?;
         let self_trait: Path = input.parse()?;
+        let foreign_trait = if input.peek(Token![for]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
         let content: ParseBuffer;
         braced!(content in input);
         let mut items = Vec::new();
@@ -557,11 +1302,220 @@ impl ItemTraitImpl {
         Ok(ItemTraitImpl {
             generics,
             self_trait,
+            foreign_trait,
+            items,
+        })
+    }
+}
+
+pub struct ItemImplExt {
+    pub attrs: Vec<Attribute>,
+    pub impl_token: Token![impl],
+    pub generics: Generics,
+    pub trait_: Option<(Path, Token![for])>,
+    pub self_ty: Type,
+    pub items: Vec<TraitImplItem>,
+}
+
+impl ItemImplExt {
+    fn parse(input: ParseStream, attrs: Vec<Attribute>) -> Result<Self> {
+        let impl_token: Token![impl] = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        let mut trait_ = None;
+        let mut self_ty: Type = input.parse()?;
+        if let Some(for_token) = input.parse::<Option<Token![for]>>()? {
+            let Type::Path(TypePath { qself: None, path }) = self_ty else {
+                return Err(Error::new(self_ty.span(), "trait path expected"));
+            };
+            trait_ = Some((path, for_token));
+            self_ty = input.parse()?;
+        }
+        generics.where_clause = input.parse()?;
+        let content: ParseBuffer;
+        braced!(content in input);
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            items.push(content.parse()?);
+        }
+        Ok(ItemImplExt {
+            attrs,
+            impl_token,
+            generics,
+            trait_,
+            self_ty,
             items,
         })
     }
 }
 
+pub struct ItemStructExt {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub struct_token: Token![struct],
+    pub ident: Ident,
+    pub generics: Generics,
+    pub fields: FieldsExt,
+    pub semi_token: Option<Token![;]>,
+}
+
+impl ItemStructExt {
+    fn parse(input: ParseStream, attrs: Vec<Attribute>) -> Result<Self> {
+        let vis: Visibility = input.parse()?;
+        let struct_token: Token![struct] = input.parse()?;
+        let ident: Ident = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        let fields;
+        let mut semi_token = None;
+        if input.peek(token::Paren) {
+            fields = FieldsExt::parse_unnamed(input)?;
+            generics.where_clause = input.parse()?;
+            semi_token = Some(input.parse()?);
+        } else {
+            generics.where_clause = input.parse()?;
+            if input.peek(Token![;]) {
+                fields = FieldsExt::Unit;
+                semi_token = Some(input.parse()?);
+            } else {
+                fields = FieldsExt::parse_named(input)?;
+            }
+        }
+        Ok(ItemStructExt {
+            attrs,
+            vis,
+            struct_token,
+            ident,
+            generics,
+            fields,
+            semi_token,
+        })
+    }
+}
+
+pub struct ItemEnumExt {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub enum_token: Token![enum],
+    pub ident: Ident,
+    pub generics: Generics,
+    pub variants: Punctuated<VariantExt, Token![,]>,
+}
+
+impl ItemEnumExt {
+    fn parse(input: ParseStream, attrs: Vec<Attribute>) -> Result<Self> {
+        let vis: Visibility = input.parse()?;
+        let enum_token: Token![enum] = input.parse()?;
+        let ident: Ident = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        generics.where_clause = input.parse()?;
+        let content: ParseBuffer;
+        braced!(content in input);
+        let variants = content.parse_terminated(VariantExt::parse, Token![,])?;
+        Ok(ItemEnumExt {
+            attrs,
+            vis,
+            enum_token,
+            ident,
+            generics,
+            variants,
+        })
+    }
+}
+
+pub struct VariantExt {
+    pub attrs: Vec<Attribute>,
+    pub ident: Ident,
+    pub fields: FieldsExt,
+    pub discriminant: Option<(Token![=], Expr)>,
+}
+
+impl Parse for VariantExt {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let ident: Ident = input.parse()?;
+        let fields = if input.peek(token::Brace) {
+            FieldsExt::parse_named(input)?
+        } else if input.peek(token::Paren) {
+            FieldsExt::parse_unnamed(input)?
+        } else {
+            FieldsExt::Unit
+        };
+        let discriminant = if input.peek(Token![=]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        Ok(VariantExt {
+            attrs,
+            ident,
+            fields,
+            discriminant,
+        })
+    }
+}
+
+// Like `Fields`, but the type of each field may be a type-level expression.
+pub enum FieldsExt {
+    Named(Punctuated<FieldExt, Token![,]>),
+    Unnamed(Punctuated<FieldExt, Token![,]>),
+    Unit,
+}
+
+impl FieldsExt {
+    fn parse_named(input: ParseStream) -> Result<Self> {
+        let content: ParseBuffer;
+        braced!(content in input);
+        Ok(FieldsExt::Named(
+            content.parse_terminated(FieldExt::parse_named, Token![,])?,
+        ))
+    }
+
+    fn parse_unnamed(input: ParseStream) -> Result<Self> {
+        let content: ParseBuffer;
+        parenthesized!(content in input);
+        Ok(FieldsExt::Unnamed(
+            content.parse_terminated(FieldExt::parse_unnamed, Token![,])?,
+        ))
+    }
+}
+
+pub struct FieldExt {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub ident: Option<Ident>,
+    pub colon_token: Option<Token![:]>,
+    pub ty: TypeLevelExpr<Type>,
+}
+
+impl FieldExt {
+    fn parse_named(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis: Visibility = input.parse()?;
+        let ident: Ident = input.parse()?;
+        let colon_token: Token![:] = input.parse()?;
+        let ty: TypeLevelExpr<Type> = input.parse()?;
+        Ok(FieldExt {
+            attrs,
+            vis,
+            ident: Some(ident),
+            colon_token: Some(colon_token),
+            ty,
+        })
+    }
+
+    fn parse_unnamed(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis: Visibility = input.parse()?;
+        let ty: TypeLevelExpr<Type> = input.parse()?;
+        Ok(FieldExt {
+            attrs,
+            vis,
+            ident: None,
+            colon_token: None,
+            ty,
+        })
+    }
+}
+
 pub struct ItemTypeExt {
     pub attrs: Vec<Attribute>,
     pub vis: Visibility,
@@ -609,7 +1563,7 @@ pub struct ItemFnExt {
 impl ItemFnExt {
     fn parse(input: ParseStream, attrs: Vec<Attribute>) -> Result<Self> {
         let vis: Visibility = input.parse()?;
-        let sig: MetaSignature = input.parse()?;
+        let sig: MetaSignature = parse_with_inline_matches(input, is_brace_group)?;
         let content: ParseBuffer;
         braced!(content in input);
         let block: TypeLevelExpr<Expr, Block> = content.parse()?;
@@ -671,13 +1625,14 @@ impl TraitImplItemType {
         let vis: Visibility = input.parse()?;
         let type_token: Token![type] = input.parse()?;
         let ident: Ident = input.parse()?;
-        let generics: Generics = input.parse()?;
+        let mut generics: Generics = input.parse()?;
         let colon_token: Option<Token![:]> = input.parse()?;
         let bounds = if colon_token.is_some() {
             parse_type_param_bounds(input)?
         } else {
             Punctuated::new()
         };
+        generics.where_clause = input.parse()?;
         input.parse::<Token![=]>()?;
         let ty: TypeLevelExpr<Type> = input.parse()?;
         input.parse::<Token![;]>()?;
@@ -699,6 +1654,8 @@ pub struct TraitImplItemConst {
     pub vis: Visibility,
     pub const_token: Token![const],
     pub ident: Ident,
+    // Only used for internal items; see `create_generic_const_trait_item`.
+    pub generics: Generics,
     pub ty: Type,
     pub expr: TypeLevelExpr<Expr>,
 }
@@ -718,6 +1675,7 @@ impl TraitImplItemConst {
             attrs,
             const_token,
             ident,
+            generics: Generics::default(),
             ty,
             expr,
         })
@@ -735,7 +1693,7 @@ pub struct TraitImplItemFn {
 impl TraitImplItemFn {
     fn parse(input: ParseStream, attrs: Vec<Attribute>) -> Result<Self> {
         let vis: Visibility = input.parse()?;
-        let sig: Signature = input.parse()?;
+        let sig: Signature = parse_with_inline_matches(input, is_brace_group)?;
         let content: ParseBuffer;
         braced!(content in input);
         let block: TypeLevelExpr<Expr, Block> = content.parse()?;
@@ -1,3 +1,4 @@
+use config::BlockConfig;
 use expr::TypeLevelLambda;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
@@ -6,9 +7,10 @@ use syn::{
     parse::{Parse, ParseStream},
     parse_macro_input,
     spanned::Spanned,
-    Error, GenericArgument, ItemFn, LitInt, Result, Token, Type,
+    Attribute, Error, GenericArgument, ItemFn, LitInt, Result, Token, Type,
 };
 
+mod config;
 mod expr;
 mod generics;
 mod helpers;
@@ -28,9 +30,22 @@ struct MetaBlock(TokenStream);
 
 impl Parse for MetaBlock {
     fn parse(input: ParseStream) -> Result<Self> {
-        let input_list: MetaItemList = input.parse()?;
-        let output_list = input_list.output()?;
-        Ok(MetaBlock(output_list.into_token_stream()))
+        let input_tokens: TokenStream = input.fork().parse()?;
+        let attrs = input.call(Attribute::parse_inner)?;
+        let mut config = BlockConfig::parse_attrs(&attrs)?;
+        config.type_traits = MetaItemList::declared_type_traits(input);
+        config.apply(|| {
+            let input_list: MetaItemList = input.parse()?;
+            // If an alias of a trait defined elsewhere needs to know the variants of that trait,
+            // we let a macro of the trait add them to the block, and expand it again.
+            if let Some((alias_ident, macro_path)) = input_list.variants_request() {
+                return Ok(MetaBlock(
+                    quote!(#macro_path!(#alias_ident, { #input_tokens });),
+                ));
+            }
+            let output_list = input_list.output()?;
+            Ok(MetaBlock(output_list.into_token_stream()))
+        })
     }
 }
 
@@ -78,3 +93,278 @@ pub fn const_test(
     output_tokens.extend(quote!(const _: () = #ident();));
     output_tokens.into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta_output(input: TokenStream) -> String {
+        match syn::parse2::<MetaBlock>(input) {
+            Ok(block) => block.0.to_string(),
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    fn meta_errors(input: TokenStream) -> Vec<String> {
+        match syn::parse2::<MetaBlock>(input) {
+            Ok(_) => panic!("error expected"),
+            Err(error) => error.into_iter().map(|error| error.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn inherent_impl_type_error() {
+        assert_eq!(
+            meta_errors(quote! {
+                impl<B: MyBool, T> Wrapper<B, T> {
+                    pub type Out = match <B> {
+                        False => T,
+                        True => (),
+                    };
+                }
+            }),
+            ["associated types are not supported in inherent impls; use a generic type alias instead"],
+        );
+    }
+
+    #[test]
+    fn free_items_keep_deprecation_warnings() {
+        let output = meta_output(quote! {
+            pub enum trait MyBool {
+                False,
+                True,
+            }
+
+            pub fn value<B: MyBool>() -> match <B> { False => u8, True => u16 } {
+                let offset = match <B> {
+                    False => 0,
+                    True => 1,
+                };
+                old_value() + offset
+            }
+
+            pub struct Wrapper<B: MyBool> {
+                pub value: match <B> { False => u8, True => u16 },
+            }
+        });
+        assert!(!output.contains("deprecated"));
+    }
+
+    #[test]
+    fn variants_of_aliased_trait_requested() {
+        let output = meta_output(quote! {
+            pub trait MyBool = meta_bool::MetaBool;
+
+            pub type Not<B: MyBool>: MyBool = match <B> {
+                True => False,
+                False => True,
+            };
+        });
+        assert!(output.starts_with("meta_bool :: __trait_variants__MetaBool ! (MyBool , {"));
+    }
+
+    #[test]
+    fn aliased_trait_variants_order() {
+        let output = meta_output(quote! {
+            #![__variants(MyBool = False, True)]
+
+            pub trait MyBool = MetaBool;
+
+            pub type Not<B: MyBool>: MyBool = match <B> {
+                True => False,
+                False => True,
+            };
+        });
+        let false_idx = output.find("False => {").unwrap();
+        let true_idx = output.find("True => {").unwrap();
+        assert!(false_idx < true_idx);
+    }
+
+    #[test]
+    fn aliased_trait_missing_variants() {
+        assert_eq!(
+            meta_errors(quote! {
+                #![__variants(MyNum = Zero, Succ<N: MyNum>)]
+
+                pub trait MyNum = MetaNum;
+
+                pub type IsZero<N: MyNum>: MetaBool = match <N> {
+                    Succ<P: MetaNum> => False,
+                };
+            }),
+            ["missing variant(s): `Zero`"],
+        );
+    }
+
+    #[test]
+    fn generic_aliased_trait_variants() {
+        assert_eq!(
+            meta_errors(quote! {
+                #![__variants(MyPair = First<T>, Second<T>)]
+
+                pub trait MyPair<T> = Pair<T>;
+
+                pub type Pick<T, P: MyPair<T>>: MetaBool = match <P> {
+                    Third => True,
+                    Second => False,
+                };
+            }),
+            [
+                "`Third` is not a variant of `MyPair`",
+                "missing variant(s): `First`"
+            ],
+        );
+    }
+
+    #[test]
+    fn missing_variants() {
+        assert_eq!(
+            meta_errors(quote! {
+                pub enum trait Color {
+                    Red,
+                    Green,
+                    Blue,
+                }
+
+                trait impl Color {
+                    pub const IS_RED: bool = match <Self> {
+                        Blue => false,
+                        Red => true,
+                    };
+                }
+            }),
+            ["missing variant(s): `Green`"],
+        );
+    }
+
+    #[test]
+    fn unreachable_arm() {
+        let output = meta_output(quote! {
+            pub enum trait Color {
+                Red,
+                Green,
+                Blue,
+            }
+
+            trait impl Color {
+                pub const IS_RED: bool = match <Self> {
+                    Green => false,
+                    Red => true,
+                    Blue => false,
+                    Red => false,
+                };
+            }
+        });
+        assert!(output.contains("unreachable arm"));
+    }
+
+    #[test]
+    fn guard_without_meta_bool_alias() {
+        assert_eq!(
+            meta_errors(quote! {
+                pub enum trait Nat {
+                    Z,
+                    S<N: Nat>,
+                }
+
+                pub type Pred<N: Nat>: Nat = match <N> {
+                    S<P: Nat> if IsSmall<P> => P,
+                    _ => Z,
+                };
+            }),
+            ["cannot infer type bound for guard; define an alias of `enum_trait_core::meta_bool::MetaBool` within this block and use it as the bound of the guard type"],
+        );
+    }
+
+    #[test]
+    fn foreign_trait_impl_type_param_error() {
+        assert_eq!(
+            meta_errors(quote! {
+                pub enum trait Wrapped<T> {
+                    Plain,
+                    Boxed,
+                }
+
+                trait impl<T> Wrapped<T> for Describe {
+                    const NAME: &'static str = "wrapped";
+                }
+            }),
+            ["implementing other traits for variants is only supported if all type and const parameters of the enum trait are trait bound parameters"],
+        );
+    }
+
+    #[test]
+    fn sealed_enum_trait() {
+        let enum_trait = quote! {
+            pub enum trait MyBool {
+                False,
+                True,
+            }
+        };
+        let output = meta_output(enum_trait.clone());
+        assert!(output.contains("mod __sealed__MyBool { pub trait Sealed { } }"));
+        assert!(output.contains("+ 'static + __sealed__MyBool :: Sealed { }"));
+        assert!(output.contains("impl __sealed__MyBool :: Sealed for False { }"));
+        assert!(output.contains("impl __sealed__MyBool :: Sealed for True { }"));
+
+        let output = meta_output(quote! {
+            #[open]
+            #enum_trait
+        });
+        assert!(!output.contains("Sealed"));
+    }
+
+    #[test]
+    fn open_trait_alias_error() {
+        assert_eq!(
+            meta_errors(quote! {
+                #[open]
+                pub trait MyBool = MetaBool;
+            }),
+            ["`#[open]` is only supported on enum traits"],
+        );
+    }
+
+    #[test]
+    fn derive_with_match_fields() {
+        assert_eq!(
+            meta_errors(quote! {
+                #[derive(Debug)]
+                pub struct Counter<B: MyBool> {
+                    pub count: match <B> {
+                        False => u8,
+                        True => u64,
+                    },
+                }
+            }),
+            ["`#[derive]` is not supported for types with type-level `match` fields; implement the traits manually instead"],
+        );
+        assert_eq!(
+            meta_errors(quote! {
+                #[derive(Clone)]
+                pub enum Value<B: MyBool, T> {
+                    Empty,
+                    Single(Option<match <B> { False => T, True => Box<T> }>),
+                }
+            }),
+            ["`#[derive]` is not supported for types with type-level `match` fields; implement the traits manually instead"],
+        );
+        let output = meta_output(quote! {
+            #[derive(Clone)]
+            pub struct Plain<T> {
+                pub value: T,
+            }
+        });
+        assert!(output.contains("# [derive (Clone)]"));
+    }
+
+    #[test]
+    fn let_binding_trait_not_inferred() {
+        assert_eq!(
+            meta_errors(quote! {
+                pub type HalfIsEven<N: MetaNum>: MetaBool = let Half = Div2<N>; in Half::IsEven;
+            }),
+            ["cannot infer the trait of `Half`; specify it, e.g. `let Half: Trait = ...;`"],
+        );
+    }
+}
@@ -38,8 +38,9 @@ impl<'a> OutputMetaItemList<'a> {
         item: TraitImplItem,
         context: &GenericsContext,
         trait_def: &ItemTraitDef,
-        variants_known: bool,
+        known_variants: Option<&[TraitVariant]>,
     ) -> Result<OutputTraitItemDesc> {
+        let own_item = Self::is_own_item(part_ident);
         match item {
             TraitImplItem::Type(type_item) => {
                 let mut trait_item = TraitItemType {
@@ -57,11 +58,14 @@ impl<'a> OutputMetaItemList<'a> {
                 let item_context = GenericsContext::WithGenerics(&trait_item.generics, &context);
                 let mut expr = Some(type_item.ty);
                 let mut dependent_idents = Vec::new();
+                let mut warnings = Vec::new();
                 let mut variants = Self::try_implement_variants(
                     &mut expr,
                     &item_context,
                     trait_def,
+                    known_variants,
                     &mut dependent_idents,
+                    &mut warnings,
                     |body, body_context, _| {
                         let mut ty = self.convert_type_level_expr_type(
                             &type_item.attrs,
@@ -73,7 +77,7 @@ impl<'a> OutputMetaItemList<'a> {
                         RemoveTypeBoundParamsFromPathArguments(&trait_def.generics)
                             .visit_type_mut(&mut ty);
                         Ok(ImplItem::Type(ImplItemType {
-                            attrs: Self::code_item_attrs(type_item.attrs.clone()),
+                            attrs: Self::impl_item_attrs(type_item.attrs.clone(), own_item),
                             vis: Visibility::Inherited,
                             defaultness: None,
                             type_token: trait_item.type_token.clone(),
@@ -95,12 +99,12 @@ impl<'a> OutputMetaItemList<'a> {
                     )?;
                     RemoveTypeBoundParamsFromPathArguments(&trait_def.generics)
                         .visit_type_mut(&mut ty);
-                    if variants_known {
+                    if known_variants.is_some() {
                         let span = ty.span();
                         // Prefer individual impls over default in trait because the latter is
                         // currently unstable.
                         let impl_item = ImplItemType {
-                            attrs: Self::code_item_attrs(type_item.attrs.clone()),
+                            attrs: Self::impl_item_attrs(type_item.attrs.clone(), own_item),
                             vis: Visibility::Inherited,
                             defaultness: None,
                             type_token: trait_item.type_token.clone(),
@@ -129,9 +133,19 @@ impl<'a> OutputMetaItemList<'a> {
                     item: TraitItem::Type(trait_item),
                     variants,
                     dependent_idents,
+                    internal: false,
+                    warnings,
                 })
             }
 
+            TraitImplItem::Const(const_item) if !const_item.generics.params.is_empty() => self
+                .create_generic_const_trait_item(
+                    part_ident,
+                    const_item,
+                    context,
+                    trait_def,
+                    known_variants,
+                ),
             TraitImplItem::Const(const_item) => {
                 let mut trait_item = TraitItemConst {
                     attrs: Self::trait_item_attrs(const_item.attrs.clone(), &const_item.vis),
@@ -147,11 +161,14 @@ impl<'a> OutputMetaItemList<'a> {
                     .visit_trait_item_const_mut(&mut trait_item);
                 let mut expr = Some(const_item.expr);
                 let mut dependent_idents = Vec::new();
+                let mut warnings = Vec::new();
                 let variants = Self::try_implement_variants(
                     &mut expr,
                     context,
                     trait_def,
+                    known_variants,
                     &mut dependent_idents,
+                    &mut warnings,
                     |body, body_context, substitutions| {
                         let mut expr = self.convert_type_level_expr_const(
                             &const_item.attrs,
@@ -167,7 +184,7 @@ impl<'a> OutputMetaItemList<'a> {
                             ty.substitute(subst_param, ParamSubstArg::Arg(subst_arg))?;
                         }
                         Ok(ImplItem::Const(ImplItemConst {
-                            attrs: Self::code_item_attrs(const_item.attrs.clone()),
+                            attrs: Self::impl_item_attrs(const_item.attrs.clone(), own_item),
                             vis: Visibility::Inherited,
                             defaultness: None,
                             const_token: trait_item.const_token.clone(),
@@ -197,10 +214,13 @@ impl<'a> OutputMetaItemList<'a> {
                     item: TraitItem::Const(trait_item),
                     variants,
                     dependent_idents,
+                    internal: false,
+                    warnings,
                 })
             }
 
-            TraitImplItem::Fn(fn_item) => {
+            TraitImplItem::Fn(mut fn_item) => {
+                self.lift_inline_matches_in_signature(part_ident, &mut fn_item.sig, context)?;
                 let mut trait_item = TraitItemFn {
                     attrs: Self::trait_item_attrs(fn_item.attrs.clone(), &fn_item.vis),
                     sig: fn_item.sig.clone(),
@@ -209,16 +229,24 @@ impl<'a> OutputMetaItemList<'a> {
                 };
                 RemoveTypeBoundParamsFromPathArguments(&trait_def.generics)
                     .visit_trait_item_fn_mut(&mut trait_item);
+                // Functions that forward their arguments, as well as declarations, cannot use
+                // patterns in parameters.
+                let mut named_sig = trait_item.sig.clone();
+                name_param_patterns(&mut named_sig);
                 let item_context =
                     GenericsContext::WithGenerics(&trait_item.sig.generics, &context);
                 let mut expr = Some(fn_item.block);
                 let mut dependent_idents = Vec::new();
+                let mut warnings = Vec::new();
                 let variants = Self::try_implement_variants(
                     &mut expr,
                     &item_context,
                     trait_def,
+                    known_variants,
                     &mut dependent_idents,
+                    &mut warnings,
                     |body, body_context, substitutions| {
+                        let forwarding = matches!(body, TypeLevelExpr::Match(_));
                         let body = match body {
                             TypeLevelExpr::Expr(body_expr) => TypeLevelExpr::Expr(Block {
                                 brace_token: Default::default(),
@@ -235,12 +263,18 @@ impl<'a> OutputMetaItemList<'a> {
                         )?;
                         RemoveTypeBoundParamsFromPathArguments(&trait_def.generics)
                             .visit_block_mut(&mut block);
-                        let mut sig = trait_item.sig.clone();
+                        let mut sig = if forwarding {
+                            named_sig.clone()
+                        } else {
+                            trait_item.sig.clone()
+                        };
                         for (subst_param, subst_arg) in substitutions {
                             sig.substitute(subst_param, ParamSubstArg::Arg(subst_arg))?;
                         }
+                        let mut attrs = Self::impl_item_attrs(fn_item.attrs.clone(), own_item);
+                        attrs.push(OutputMetaItemList::variant_fn_lint_attr());
                         Ok(ImplItem::Fn(ImplItemFn {
-                            attrs: Self::code_item_attrs(fn_item.attrs.clone()),
+                            attrs,
                             vis: Visibility::Inherited,
                             defaultness: None,
                             sig,
@@ -249,17 +283,24 @@ impl<'a> OutputMetaItemList<'a> {
                     },
                 )?;
                 if variants.is_none() {
+                    let expr = expr.unwrap();
+                    let forwarding = matches!(expr, TypeLevelExpr::Match(_));
                     let mut block = self.convert_type_level_expr_fn(
                         &fn_item.attrs,
                         part_ident,
-                        expr.unwrap(),
-                        context,
+                        expr,
+                        &item_context,
                         &fn_item.sig,
                     )?;
                     RemoveTypeBoundParamsFromPathArguments(&trait_def.generics)
                         .visit_block_mut(&mut block);
+                    if forwarding {
+                        trait_item.sig = named_sig;
+                    }
                     trait_item.default = Some(block);
                     trait_item.semi_token = None;
+                } else {
+                    trait_item.sig = named_sig;
                 }
                 trait_def.collect_dependencies_in_generics(
                     &fn_item.sig.generics,
@@ -270,18 +311,188 @@ impl<'a> OutputMetaItemList<'a> {
                     item: TraitItem::Fn(trait_item),
                     variants,
                     dependent_idents,
+                    internal: false,
+                    warnings,
                 })
             }
         }
     }
 
+    // Constants cannot have generic parameters, so we implement them as generic associated types
+    // that refer to a tuple of `Self` and the generic arguments. The actual value is defined in a
+    // helper trait that we implement on that tuple for each variant.
+    fn create_generic_const_trait_item(
+        &mut self,
+        part_ident: &mut Option<Ident>,
+        const_item: TraitImplItemConst,
+        context: &GenericsContext,
+        trait_def: &ItemTraitDef,
+        known_variants: Option<&[TraitVariant]>,
+    ) -> Result<OutputTraitItemDesc> {
+        let own_item = Self::is_own_item(part_ident);
+        let helper_ident = Self::const_helper_ident(&trait_def.ident, &const_item.ident);
+        let mut helper_generics = trait_def.generics.extract_generics();
+        for param in &mut helper_generics.params {
+            if let GenericParam::Type(type_param) = param {
+                type_param.colon_token = None;
+                type_param.bounds = Punctuated::new();
+            }
+        }
+        helper_generics.where_clause = None;
+        let helper_args = generic_args(&helper_generics);
+        let mut trait_item = TraitItemType {
+            attrs: Self::trait_item_attrs(const_item.attrs.clone(), &const_item.vis),
+            type_token: Default::default(),
+            ident: const_item.ident.clone(),
+            generics: const_item.generics.clone(),
+            colon_token: Some(Default::default()),
+            bounds: parse_quote!(#helper_ident #helper_args),
+            default: None,
+            semi_token: Default::default(),
+        };
+        RemoveTypeBoundParamsFromPathArguments(&trait_def.generics)
+            .visit_trait_item_type_mut(&mut trait_item);
+        let mut tuple_elems: Punctuated<Type, Token![,]> = Punctuated::new();
+        tuple_elems.push(parse_quote!(Self));
+        for param in &const_item.generics.params {
+            tuple_elems.push(match param {
+                GenericParam::Lifetime(lifetime_param) => {
+                    let lifetime = &lifetime_param.lifetime;
+                    parse_quote!(&#lifetime ())
+                }
+                GenericParam::Type(type_param) => {
+                    let ident = &type_param.ident;
+                    parse_quote!(#ident)
+                }
+                GenericParam::Const(const_param) => {
+                    return Err(Error::new(
+                        const_param.span(),
+                        "matching within constants that depend on const params is not supported",
+                    ));
+                }
+            });
+        }
+        let tuple_ty = Type::Tuple(TypeTuple {
+            paren_token: Default::default(),
+            elems: tuple_elems,
+        });
+
+        let item_context = GenericsContext::WithGenerics(&const_item.generics, context);
+        let mut expr = Some(const_item.expr);
+        let mut dependent_idents = Vec::new();
+        let mut warnings = Vec::new();
+        let mut values = Vec::new();
+        let mut variants = Self::try_implement_variants(
+            &mut expr,
+            &item_context,
+            trait_def,
+            known_variants,
+            &mut dependent_idents,
+            &mut warnings,
+            |body, body_context, _| {
+                let mut value = self.convert_type_level_expr_const(
+                    &const_item.attrs,
+                    part_ident,
+                    body,
+                    body_context,
+                    &const_item.ty,
+                )?;
+                RemoveTypeBoundParamsFromPathArguments(&trait_def.generics)
+                    .visit_expr_mut(&mut value);
+                values.push(value);
+                Ok(ImplItem::Type(ImplItemType {
+                    attrs: Self::impl_item_attrs(const_item.attrs.clone(), own_item),
+                    vis: Visibility::Inherited,
+                    defaultness: None,
+                    type_token: Default::default(),
+                    ident: trait_item.ident.clone(),
+                    generics: trait_item.generics.clone(),
+                    eq_token: Default::default(),
+                    ty: tuple_ty.clone(),
+                    semi_token: Default::default(),
+                }))
+            },
+        )?;
+        let Some((variant_impls, _)) = &mut variants else {
+            return Err(Error::new(
+                expr.span(),
+                "generic constants must match on `Self`",
+            ));
+        };
+        // Extensions cannot implement the helper trait for their variants, so generic constants
+        // have no implementation for them.
+        if let Some(default_idx) = variant_impls
+            .iter()
+            .position(|(impl_variant, _)| impl_variant.is_none())
+        {
+            variant_impls.remove(default_idx);
+            values.remove(default_idx);
+        }
+
+        let vis = &trait_def.vis;
+        let ty = &const_item.ty;
+        self.0.push(OutputMetaItem::Item(parse_quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            #vis trait #helper_ident #helper_generics {
+                const VALUE: #ty;
+            }
+        }));
+        for ((impl_variant, _), mut value) in variant_impls.iter().zip(values) {
+            let Some(impl_variant) = impl_variant else {
+                unreachable!();
+            };
+            let variant_ident = &impl_variant.variant.ident;
+            let variant_args = generic_args(&impl_variant.variant.generics);
+            let self_arg = GenericArgument::Type(parse_quote!(#variant_ident #variant_args));
+            let self_param = self_type_param(None, Punctuated::new());
+            let mut self_ty = tuple_ty.clone();
+            self_ty.substitute(&self_param, ParamSubstArg::Arg(&self_arg))?;
+            value.substitute(&self_param, ParamSubstArg::Arg(&self_arg))?;
+            let mut impl_generics = impl_variant.impl_generics.clone();
+            for param in impl_variant
+                .variant
+                .generics
+                .params
+                .iter()
+                .chain(&const_item.generics.params)
+            {
+                let mut param = param.clone();
+                RemoveTypeBoundParamsFromPathArguments(&trait_def.generics)
+                    .visit_generic_param_mut(&mut param);
+                impl_generics.params.push(param);
+            }
+            let impl_generics = build_generics(impl_generics.params);
+            let trait_args = &impl_variant.trait_args;
+            let attrs = Self::impl_item_attrs(const_item.attrs.clone(), own_item);
+            self.0.push(OutputMetaItem::Item(parse_quote! {
+                #(#attrs)*
+                impl #impl_generics #helper_ident #trait_args for #self_ty {
+                    const VALUE: #ty = #value;
+                }
+            }));
+        }
+
+        Ok(OutputTraitItemDesc {
+            item: TraitItem::Type(trait_item),
+            variants,
+            dependent_idents,
+            internal: false,
+            warnings,
+        })
+    }
+
+    fn const_helper_ident(trait_ident: &Ident, item_ident: &Ident) -> Ident {
+        Ident::new(&format!("__{trait_ident}{item_ident}"), Span::call_site())
+    }
+
     fn get_self_match<E, Ex>(
         expr: &mut Option<TypeLevelExpr<E, Ex>>,
     ) -> Option<TypeLevelExprMatch<TypeLevelExpr<E>>> {
         let Some(TypeLevelExpr::Match(match_expr)) = expr else {
             return None;
         };
-        if !type_is_ident(match_expr.types.last().unwrap(), SELF_TYPE_NAME) {
+        if !type_is_ident(&match_expr.types.last().unwrap().ty, SELF_TYPE_NAME) {
             return None;
         }
         let Some(TypeLevelExpr::Match(match_expr)) = take(expr) else {
@@ -290,11 +501,13 @@ impl<'a> OutputMetaItemList<'a> {
         Some(match_expr)
     }
 
-    fn try_implement_variants<E: Substitutable + ToTokens, Ex: Substitutable>(
+    fn try_implement_variants<E: Substitutable + Clone + ToTokens, Ex: Substitutable>(
         expr: &mut Option<TypeLevelExpr<E, Ex>>,
         context: &GenericsContext,
         trait_def: &ItemTraitDef,
+        known_variants: Option<&[TraitVariant]>,
         dependent_idents: &mut Vec<Ident>,
+        warnings: &mut Vec<OutputWarning>,
         f: impl FnMut(
             TypeLevelExpr<E>,
             &GenericsContext,
@@ -303,20 +516,33 @@ impl<'a> OutputMetaItemList<'a> {
     ) -> Result<Option<(Vec<(Option<ImplVariant>, ImplItem)>, Span)>> {
         if let Some(match_expr) = Self::get_self_match(expr) {
             let variants_span = match_expr.span();
-            let variants_impls =
-                Self::implement_variants(match_expr, context, trait_def, dependent_idents, f)?;
+            let variants_impls = Self::implement_variants(
+                match_expr,
+                context,
+                trait_def,
+                known_variants,
+                dependent_idents,
+                warnings,
+                f,
+            )?;
             Ok(Some((variants_impls, variants_span)))
         } else {
             Ok(None)
         }
     }
 
-    fn implement_variants<E: Substitutable>(
-        match_expr: TypeLevelExprMatch<E>,
+    fn implement_variants<E: Substitutable + Clone>(
+        match_expr: TypeLevelExprMatch<TypeLevelExpr<E>>,
         context: &GenericsContext,
         trait_def: &ItemTraitDef,
+        known_variants: Option<&[TraitVariant]>,
         dependent_idents: &mut Vec<Ident>,
-        mut f: impl FnMut(E, &GenericsContext, &[(&GenericParam, GenericArgument)]) -> Result<ImplItem>,
+        warnings: &mut Vec<OutputWarning>,
+        mut f: impl FnMut(
+            TypeLevelExpr<E>,
+            &GenericsContext,
+            &[(&GenericParam, GenericArgument)],
+        ) -> Result<ImplItem>,
     ) -> Result<Vec<(Option<ImplVariant>, ImplItem)>> {
         let mut free_params: Vec<GenericParam> = trait_def
             .generics
@@ -324,52 +550,167 @@ impl<'a> OutputMetaItemList<'a> {
             .params
             .into_iter()
             .collect();
+        // For each type except the last, the trait parameter that it corresponds to, or `None` if
+        // the type is not a trait argument (e.g. if it is a parameter of the item or a compound
+        // type) and is matched in a nested `match` expression within each variant instead. The
+        // latter also applies to trait arguments that are matched using nested patterns.
         let mut matched_params = Vec::new();
+        let mut nested_types = Punctuated::new();
         let types_len = match_expr.types.len();
-        let mut ty_iter = match_expr.types.iter();
-        let mut ty = ty_iter.next().unwrap();
-        while let Some(next_ty) = ty_iter.next() {
-            let Some(match_ident) = get_type_ident(&ty) else {
-                return Err(Error::new(
-                    ty.span(),
-                    "type matching is currently only supported on variables",
-                ));
-            };
-            let Some(generic_idx) = free_params.iter().position(|param| {
-                if let GenericParam::Type(type_param) = param {
-                    &type_param.ident == match_ident
-                } else {
-                    false
-                }
-            }) else {
-                let free_param_idents = free_params
+        for (type_idx, match_type) in match_expr.types.iter().take(types_len - 1).enumerate() {
+            let has_nested_patterns = match_expr.arms.iter().any(|arm| {
+                arm.selectors
                     .iter()
-                    .filter_map(|param| {
+                    .nth(type_idx)
+                    .is_some_and(TypeLevelArmSelector::has_nested_patterns)
+            });
+            let generic_idx = get_type_ident(&match_type.ty)
+                .filter(|_| !has_nested_patterns)
+                .and_then(|match_ident| {
+                    free_params.iter().position(|param| {
                         if let GenericParam::Type(type_param) = param {
-                            Some(format!("`{}`", type_param.ident))
+                            &type_param.ident == match_ident
                         } else {
-                            None
+                            false
                         }
                     })
-                    .collect::<Vec<String>>()
-                    .join(", ");
+                });
+            if let Some(generic_idx) = generic_idx {
+                matched_params.push(Some(free_params.remove(generic_idx)));
+            } else {
+                matched_params.push(None);
+                nested_types.push(match_type.clone());
+            }
+        }
+
+        // Group the arms by the selectors that are handled at the trait level, so that we output a
+        // single impl item per group. Within each group, the remaining selectors are matched by
+        // a nested `match` expression.
+        let mut arm_groups: Vec<(Vec<TypeLevelArmSelector>, Vec<_>)> = Vec::new();
+        for arm in &match_expr.arms {
+            if arm.selectors.len() != types_len {
                 return Err(Error::new(
-                    ty.span(),
-                    format!("combined matching is currently only supported for trait arguments (`{match_ident}` not found in [{free_param_idents}])")));
+                    arm.selectors.span(),
+                    format!("exactly {types_len} selector(s) expected"),
+                ));
+            }
+        }
+        let mut arms = match_expr.arms;
+        let arm_variants = Self::variants_listed_in_arms(&arms);
+        for arm in &mut arms {
+            Self::prepare_nested_patterns(arm, trait_def, known_variants, &arm_variants)?;
+            if let Some(selector) = arm.selectors.last_mut() {
+                Self::replace_aliased_trait_bounds(selector, trait_def);
+            }
+        }
+        // In open enum traits, the arms with a default selector in the last position also apply
+        // to variants that are added by extensions (see `output_extend_macro`). Extensions are
+        // only supported if the trait has no parameters, so all other types are nested.
+        let default_arms: Vec<_> = if trait_def.open && trait_def.generics.params.is_empty() {
+            arms.iter()
+                .filter(|arm| {
+                    matches!(
+                        arm.selectors.last(),
+                        Some(TypeLevelArmSelector::Default { .. })
+                    )
+                })
+                .map(|arm| {
+                    let mut arm = arm.clone();
+                    arm.selectors = take(&mut arm.selectors)
+                        .into_iter()
+                        .take(types_len - 1)
+                        .collect();
+                    arm
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let arms =
+            Self::expand_default_variant_selectors(arms, trait_def, known_variants, warnings)?;
+        let has_guards = arms.iter().any(|arm| arm.guard.is_some());
+
+        // Nested patterns within the last selector are matched in additional nested columns, each
+        // of which is identified by the variant and the index of the corresponding parameter.
+        let mut pattern_columns: Vec<(Ident, usize)> = Vec::new();
+        for arm in &arms {
+            if let Some(TypeLevelArmSelector::Specific { ident, nested, .. }) = arm.selectors.last()
+            {
+                for (param_idx, nested_selector) in nested {
+                    if !matches!(nested_selector, TypeLevelArmSelector::Default { .. })
+                        && !pattern_columns
+                            .iter()
+                            .any(|(column_ident, column_param_idx)| {
+                                column_ident == ident && column_param_idx == param_idx
+                            })
+                    {
+                        pattern_columns.push((ident.clone(), *param_idx));
+                    }
+                }
+            }
+        }
+
+        for arm in arms {
+            let mut selectors = Vec::new();
+            let mut nested_selectors = Punctuated::new();
+            for (selector_idx, selector) in arm.selectors.into_iter().enumerate() {
+                if matches!(matched_params.get(selector_idx), Some(None)) {
+                    nested_selectors.push(selector);
+                } else {
+                    selectors.push(selector);
+                }
+            }
+            for (column_ident, column_param_idx) in &pattern_columns {
+                let mut nested_selector = None;
+                if let Some(TypeLevelArmSelector::Specific { ident, nested, .. }) = selectors.last()
+                {
+                    if ident == column_ident {
+                        nested_selector = nested
+                            .iter()
+                            .find(|(param_idx, _)| param_idx == column_param_idx)
+                            .map(|(_, nested_selector)| nested_selector.clone());
+                    }
+                }
+                nested_selectors.push(nested_selector.unwrap_or(TypeLevelArmSelector::Default {
+                    underscore_token: Default::default(),
+                }));
+            }
+            let mut nested_arm = TypeLevelArm {
+                selectors: nested_selectors,
+                guard: arm.guard,
+                fat_arrow_token: arm.fat_arrow_token,
+                body: arm.body,
+                comma_token: Some(Default::default()),
             };
-            matched_params.push(free_params.remove(generic_idx));
-            ty = next_ty;
+            if !nested_types.is_empty() || !pattern_columns.is_empty() || has_guards {
+                if let Some((group_selectors, group_arms)) =
+                    arm_groups.iter_mut().find(|(group_selectors, _)| {
+                        Self::selectors_coincide(group_selectors, &selectors)
+                    })
+                {
+                    let group_generics = Self::selector_generics(group_selectors);
+                    let arm_generics = Self::selector_generics(&selectors);
+                    let mut expected_generics = group_generics.clone();
+                    rename_all_params(&mut expected_generics, &arm_generics)?;
+                    check_token_equality(&arm_generics, &expected_generics)?;
+                    nested_arm.substitute_all_params(&arm_generics, &group_generics)?;
+                    group_arms.push(nested_arm);
+                    continue;
+                }
+            }
+            arm_groups.push((selectors, vec![nested_arm]));
         }
-        match_expr
-            .arms
+
+        let mut variant_impls = arm_groups
             .into_iter()
-            .map(|mut arm| {
-                if arm.selectors.len() != types_len {
-                    return Err(Error::new(
-                        arm.selectors.span(),
-                        format!("exactly {types_len} selector(s) expected"),
-                    ));
-                }
+            .map(|(selectors, mut arms)| {
+                let group_nested_types = Self::select_pattern_columns(
+                    &nested_types,
+                    &pattern_columns,
+                    &selectors,
+                    &mut arms,
+                );
+                let mut body = Self::nested_match_body(&group_nested_types, arms, warnings)?;
                 let mut impl_generic_params = Punctuated::new();
                 let mut trait_args = Punctuated::new();
                 for param in &free_params {
@@ -377,13 +718,13 @@ impl<'a> OutputMetaItemList<'a> {
                     trait_args.push(generic_param_arg(param, None));
                 }
                 let mut substitutions = Vec::new();
-                let mut selector_iter = arm.selectors.into_iter();
-                let mut selector = selector_iter.next().unwrap();
-                let mut matched_param_iter = matched_params.iter();
-                while let Some(next_selector) = selector_iter.next() {
-                    let matched_param = matched_param_iter.next().unwrap();
-                    match selector {
-                        TypeLevelArmSelector::Specific { ident, generics } => {
+                let mut selector_generic_params = Punctuated::new();
+                let mut selector_iter = selectors.into_iter();
+                for matched_param in matched_params.iter().flatten() {
+                    match selector_iter.next().unwrap() {
+                        TypeLevelArmSelector::Specific {
+                            ident, generics, ..
+                        } => {
                             trait_def.collect_dependencies_in_generics(
                                 &generics,
                                 &mut None,
@@ -396,6 +737,8 @@ impl<'a> OutputMetaItemList<'a> {
                                     .visit_generic_param_mut(&mut param);
                                 impl_generic_params.push(param);
                             }
+                            selector_generic_params.extend(generics.params);
+                            body.substitute(matched_param, ParamSubstArg::Arg(&arg))?;
                             trait_args.push(arg.clone());
                             substitutions.push((matched_param, arg));
                         }
@@ -404,20 +747,23 @@ impl<'a> OutputMetaItemList<'a> {
                             trait_args.push(generic_param_arg(matched_param, None));
                         }
                     }
-                    selector = next_selector;
                 }
-                match selector {
+                match selector_iter.next().unwrap() {
                     TypeLevelArmSelector::Specific {
                         ident,
                         mut generics,
+                        ..
                     } => {
                         rename_conflicting_params(
                             &mut generics,
                             |param| Ok(param_context_name_conflict(param, &context)),
-                            |subst| arm.body.substitute_impl(subst),
+                            |subst| body.substitute_impl(subst),
                         )?;
-                        let body_context = GenericsContext::WithGenerics(&generics, &context);
-                        let impl_item = f(arm.body, &body_context, &substitutions)?;
+                        selector_generic_params.extend(generics.params.iter().cloned());
+                        let selector_generics = build_generics(selector_generic_params);
+                        let body_context =
+                            GenericsContext::WithGenerics(&selector_generics, &context);
+                        let impl_item = f(body, &body_context, &substitutions)?;
                         Ok((
                             Some(ImplVariant {
                                 impl_generics: build_generics(impl_generic_params),
@@ -431,13 +777,430 @@ impl<'a> OutputMetaItemList<'a> {
                             impl_item,
                         ))
                     }
-                    TypeLevelArmSelector::Default { underscore_token } => Err(Error::new(
-                        underscore_token.span(),
-                        "default selector only supported for trait arguments",
-                    )),
+                    TypeLevelArmSelector::Default { .. } => unreachable!(),
                 }
             })
-            .collect::<Result<_>>()
+            .collect::<Result<Vec<_>>>()?;
+
+        if !default_arms.is_empty() {
+            // Warnings have already been reported for the expanded arms.
+            let body = Self::nested_match_body(&nested_types, default_arms, &mut Vec::new())?;
+            variant_impls.push((None, f(body, context, &[])?));
+        }
+
+        Ok(variant_impls)
+    }
+
+    // Collects the variants that are listed by arms without nested patterns in their last
+    // selector, which serve as the known variants for nested patterns if no previous `match`
+    // expression determined them. A parameter without bounds that has the same name as a
+    // listed variant is a nested pattern, as in `Succ<Zero>`.
+    fn variants_listed_in_arms<E>(arms: &[TypeLevelArm<E>]) -> Vec<TraitVariant> {
+        let idents: Vec<&Ident> = arms
+            .iter()
+            .filter_map(|arm| match arm.selectors.last() {
+                Some(TypeLevelArmSelector::Specific { ident, .. }) => Some(ident),
+                _ => None,
+            })
+            .collect();
+        let mut variants: Vec<TraitVariant> = Vec::new();
+        for arm in arms {
+            let Some(TypeLevelArmSelector::Specific {
+                ident,
+                generics,
+                nested,
+            }) = arm.selectors.last()
+            else {
+                continue;
+            };
+            if nested.is_empty()
+                && !generics.type_params().any(|type_param| {
+                    type_param.colon_token.is_none() && idents.contains(&&type_param.ident)
+                })
+                && !variants.iter().any(|variant| &variant.ident == ident)
+            {
+                variants.push(TraitVariant {
+                    attrs: Vec::new(),
+                    ident: ident.clone(),
+                    generics: generics.clone(),
+                });
+            }
+        }
+        variants
+    }
+
+    // Prepares the nested patterns within the selectors of an arm. In the last selector, bare
+    // identifiers that refer to known variants are nested patterns as well, and the parameters
+    // that stand in for nested patterns receive the bounds from the variant definition. If the
+    // variants are not known yet, the variants listed in the arms are used instead.
+    fn prepare_nested_patterns<E>(
+        arm: &mut TypeLevelArm<E>,
+        trait_def: &ItemTraitDef,
+        known_variants: Option<&[TraitVariant]>,
+        arm_variants: &[TraitVariant],
+    ) -> Result<()> {
+        let variants = known_variants.unwrap_or(arm_variants);
+        if let Some(TypeLevelArmSelector::Specific {
+            generics, nested, ..
+        }) = arm.selectors.last_mut()
+        {
+            for (param_idx, param) in generics.params.iter().enumerate() {
+                if let GenericParam::Type(TypeParam {
+                    ident: param_ident,
+                    colon_token: None,
+                    eq_token: None,
+                    ..
+                }) = param
+                {
+                    if variants.iter().any(|variant| &variant.ident == param_ident)
+                        && !nested
+                            .iter()
+                            .any(|(nested_idx, _)| *nested_idx == param_idx)
+                    {
+                        nested.push((
+                            param_idx,
+                            TypeLevelArmSelector::Specific {
+                                ident: param_ident.clone(),
+                                generics: Generics::default(),
+                                nested: Vec::new(),
+                            },
+                        ));
+                    }
+                }
+            }
+            nested.sort_by_key(|(param_idx, _)| *param_idx);
+        }
+
+        // Give all parameters that stand in for nested patterns distinct names.
+        let mut nested_param_idx = 0;
+        for selector in &mut arm.selectors {
+            if let TypeLevelArmSelector::Specific {
+                generics, nested, ..
+            } = selector
+            {
+                for (param_idx, _) in nested.iter() {
+                    if let GenericParam::Type(type_param) = &mut generics.params[*param_idx] {
+                        type_param.ident = Ident::new(
+                            &format!("__Nested{nested_param_idx}"),
+                            type_param.ident.span(),
+                        );
+                        nested_param_idx += 1;
+                    }
+                }
+            }
+        }
+
+        let Some(TypeLevelArmSelector::Specific {
+            ident,
+            generics,
+            nested,
+        }) = arm.selectors.last_mut()
+        else {
+            return Ok(());
+        };
+        let Some((_, first_nested_selector)) = nested.first() else {
+            return Ok(());
+        };
+        let Some(variant) = variants.iter().find(|variant| &variant.ident == ident) else {
+            if known_variants.is_some() {
+                // Reported later.
+                return Ok(());
+            }
+            return Err(Error::new(
+                first_nested_selector.span(),
+                format!(
+                    "parameters of `{ident}` not known yet; an arm or a previous `match` expression on `{}` must list them explicitly",
+                    &trait_def.ident
+                ),
+            ));
+        };
+        // If the parameters of the variant do not match, this is reported later.
+        if variant.generics.params.len() == generics.params.len() {
+            // A wildcard can also stand in for a const param, e.g. in `Lit<_>`.
+            for (param_idx, nested_selector) in nested.iter() {
+                if let (
+                    TypeLevelArmSelector::Default { .. },
+                    GenericParam::Type(param),
+                    GenericParam::Const(variant_param),
+                ) = (
+                    nested_selector,
+                    &generics.params[*param_idx],
+                    &variant.generics.params[*param_idx],
+                ) {
+                    generics.params[*param_idx] = GenericParam::Const(ConstParam {
+                        ident: param.ident.clone(),
+                        default: None,
+                        eq_token: None,
+                        ..variant_param.clone()
+                    });
+                }
+            }
+            let mut variant_generics = variant.generics.clone();
+            rename_all_params(&mut variant_generics, generics)?;
+            for (param_idx, _) in nested.iter() {
+                if let (GenericParam::Type(param), GenericParam::Type(variant_param)) = (
+                    &mut generics.params[*param_idx],
+                    &variant_generics.params[*param_idx],
+                ) {
+                    param.colon_token = variant_param.colon_token;
+                    param.bounds = variant_param.bounds.clone();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Replaces bounds referring to the aliased trait within a selector on an alias, including
+    // those of nested patterns, so that the arms coincide with the expanded default selectors.
+    fn replace_aliased_trait_bounds(selector: &mut TypeLevelArmSelector, trait_def: &ItemTraitDef) {
+        if let TypeLevelArmSelector::Specific {
+            generics, nested, ..
+        } = selector
+        {
+            trait_def.replace_aliased_trait_bounds(generics);
+            for (_, nested_selector) in nested {
+                Self::replace_aliased_trait_bounds(nested_selector, trait_def);
+            }
+        }
+    }
+
+    // Replaces each arm with a default selector in the last position by one arm per known
+    // variant, omitting variants that are fully covered by previous arms.
+    fn expand_default_variant_selectors<E: Clone>(
+        arms: Vec<TypeLevelArm<E>>,
+        trait_def: &ItemTraitDef,
+        known_variants: Option<&[TraitVariant]>,
+        warnings: &mut Vec<OutputWarning>,
+    ) -> Result<Vec<TypeLevelArm<E>>> {
+        let mut expanded_arms: Vec<TypeLevelArm<E>> = Vec::new();
+        for arm in arms {
+            let Some(TypeLevelArmSelector::Default { underscore_token }) = arm.selectors.last()
+            else {
+                expanded_arms.push(arm);
+                continue;
+            };
+            let Some(known_variants) = known_variants else {
+                return Err(Error::new(
+                    underscore_token.span(),
+                    format!(
+                        "variants of `{}` not known yet; a previous `match` expression must list them explicitly",
+                        &trait_def.ident
+                    ),
+                ));
+            };
+            let span = underscore_token.span();
+            let prev_len = expanded_arms.len();
+            for variant in known_variants {
+                let mut variant_arm = arm.clone();
+                let mut ident = variant.ident.clone();
+                ident.set_span(span);
+                *variant_arm.selectors.last_mut().unwrap() = TypeLevelArmSelector::Specific {
+                    ident,
+                    generics: variant.generics.clone(),
+                    nested: Vec::new(),
+                };
+                if !expanded_arms.iter().any(|prev_arm| {
+                    prev_arm.guard.is_none()
+                        && Self::selectors_cover(&prev_arm.selectors, &variant_arm.selectors)
+                }) {
+                    expanded_arms.push(variant_arm);
+                }
+            }
+            if expanded_arms.len() == prev_len {
+                warnings.push(OutputWarning::new(span, "unreachable arm"));
+            }
+        }
+        Ok(expanded_arms)
+    }
+
+    fn selectors_cover(
+        selectors: &Punctuated<TypeLevelArmSelector, Token![,]>,
+        other_selectors: &Punctuated<TypeLevelArmSelector, Token![,]>,
+    ) -> bool {
+        selectors
+            .iter()
+            .zip(other_selectors)
+            .all(|pair| match pair {
+                (TypeLevelArmSelector::Default { .. }, _) => true,
+                (
+                    TypeLevelArmSelector::Specific { ident, nested, .. },
+                    TypeLevelArmSelector::Specific {
+                        ident: other_ident, ..
+                    },
+                ) => {
+                    ident == other_ident
+                        && nested.iter().all(|(_, nested_selector)| {
+                            matches!(nested_selector, TypeLevelArmSelector::Default { .. })
+                        })
+                }
+                _ => false,
+            })
+    }
+
+    fn selectors_coincide(
+        selectors: &[TypeLevelArmSelector],
+        other_selectors: &[TypeLevelArmSelector],
+    ) -> bool {
+        selectors
+            .iter()
+            .zip(other_selectors)
+            .all(|pair| match pair {
+                (
+                    TypeLevelArmSelector::Specific {
+                        ident, generics, ..
+                    },
+                    TypeLevelArmSelector::Specific {
+                        ident: other_ident,
+                        generics: other_generics,
+                        ..
+                    },
+                ) => ident == other_ident && generics.params.len() == other_generics.params.len(),
+                (TypeLevelArmSelector::Default { .. }, TypeLevelArmSelector::Default { .. }) => {
+                    true
+                }
+                _ => false,
+            })
+    }
+
+    fn selector_generics(selectors: &[TypeLevelArmSelector]) -> Generics {
+        let mut params = Punctuated::new();
+        for selector in selectors {
+            if let TypeLevelArmSelector::Specific { generics, .. } = selector {
+                params.extend(generics.params.iter().cloned());
+            }
+        }
+        build_generics(params)
+    }
+
+    // Determines the types to match on within a group of arms, omitting the columns for nested
+    // patterns that do not apply to the variant of the group or only contain default selectors.
+    fn select_pattern_columns<E>(
+        nested_types: &Punctuated<TypeLevelMatchType, Token![,]>,
+        pattern_columns: &[(Ident, usize)],
+        selectors: &[TypeLevelArmSelector],
+        arms: &mut Vec<TypeLevelArm<E>>,
+    ) -> Punctuated<TypeLevelMatchType, Token![,]> {
+        let mut group_nested_types = nested_types.clone();
+        let mut selected_columns = Vec::new();
+        if let Some(TypeLevelArmSelector::Specific {
+            ident, generics, ..
+        }) = selectors.last()
+        {
+            for (column_idx, (column_ident, column_param_idx)) in pattern_columns.iter().enumerate()
+            {
+                let selector_idx = nested_types.len() + column_idx;
+                if column_ident != ident
+                    || arms.iter().all(|arm| {
+                        matches!(
+                            arm.selectors[selector_idx],
+                            TypeLevelArmSelector::Default { .. }
+                        )
+                    })
+                {
+                    continue;
+                }
+                if let Some(GenericParam::Type(type_param)) =
+                    generics.params.iter().nth(*column_param_idx)
+                {
+                    group_nested_types.push(TypeLevelMatchType {
+                        ty: Type::Path(TypePath {
+                            qself: None,
+                            path: type_param.ident.clone().into(),
+                        }),
+                        colon_token: None,
+                        bounds: Punctuated::new(),
+                    });
+                    selected_columns.push(selector_idx);
+                }
+            }
+        }
+        if selected_columns.len() < pattern_columns.len() {
+            for arm in arms {
+                arm.selectors = take(&mut arm.selectors)
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(selector_idx, _)| {
+                        *selector_idx < nested_types.len()
+                            || selected_columns.contains(selector_idx)
+                    })
+                    .map(|(_, selector)| selector)
+                    .collect();
+            }
+        }
+        group_nested_types
+    }
+
+    fn nested_match_body<E: Clone>(
+        nested_types: &Punctuated<TypeLevelMatchType, Token![,]>,
+        mut arms: Vec<TypeLevelArm<TypeLevelExpr<E>>>,
+        warnings: &mut Vec<OutputWarning>,
+    ) -> Result<TypeLevelExpr<E>> {
+        if arms[0]
+            .selectors
+            .iter()
+            .all(|selector| matches!(selector, TypeLevelArmSelector::Default { .. }))
+        {
+            // Also covers the case where there is nothing left to match.
+            if let Some((if_token, guard)) = take(&mut arms[0].guard) {
+                // If the guard is not satisfied, the remaining arms apply. Guards of arms with
+                // specific selectors are handled when converting the nested `match` expression.
+                if arms.len() < 2 {
+                    return Err(Error::new(
+                        if_token.span,
+                        "missing arm for the case that the guard is not satisfied",
+                    ));
+                }
+                let fallback = Self::nested_match_body(nested_types, arms.split_off(1), warnings)?;
+                return Ok(TypeLevelExpr::Match(TypeLevelExprMatch {
+                    match_token: Default::default(),
+                    lt_token: Default::default(),
+                    types: Punctuated::from_iter([TypeLevelMatchType {
+                        ty: guard,
+                        colon_token: None,
+                        bounds: Punctuated::new(),
+                    }]),
+                    gt_token: Default::default(),
+                    brace_token: Default::default(),
+                    arms: vec![
+                        Self::guard_arm("False", fallback, if_token.span),
+                        Self::guard_arm("True", arms.swap_remove(0).body, if_token.span),
+                    ],
+                    guard_if_token: Some(if_token),
+                }));
+            }
+            // Any further arms are unreachable.
+            for arm in &arms[1..] {
+                warnings.push(OutputWarning::new(arm.selectors.span(), "unreachable arm"));
+            }
+            return Ok(arms.swap_remove(0).body);
+        }
+        Ok(TypeLevelExpr::Match(TypeLevelExprMatch {
+            match_token: Default::default(),
+            lt_token: Default::default(),
+            types: nested_types.clone(),
+            gt_token: Default::default(),
+            brace_token: Default::default(),
+            arms,
+            guard_if_token: None,
+        }))
+    }
+
+    fn guard_arm<E>(
+        variant_name: &str,
+        body: TypeLevelExpr<E>,
+        span: Span,
+    ) -> TypeLevelArm<TypeLevelExpr<E>> {
+        TypeLevelArm {
+            selectors: Punctuated::from_iter([TypeLevelArmSelector::Specific {
+                ident: Ident::new(variant_name, span),
+                generics: Generics::default(),
+                nested: Vec::new(),
+            }]),
+            guard: None,
+            fat_arrow_token: Default::default(),
+            body,
+            comma_token: Some(Default::default()),
+        }
     }
 
     fn create_arm_arg(ident: &Ident, generics: &Generics) -> GenericArgument {
@@ -459,7 +1222,7 @@ impl<'a> OutputMetaItemList<'a> {
         context: &GenericsContext,
         bounds: &TypeParamBounds,
     ) -> Result<Type> {
-        self.convert_type_level_expr(
+        let mut ty = self.convert_type_level_expr(
             part_ident,
             expr,
             bounds.clone(),
@@ -476,7 +1239,43 @@ impl<'a> OutputMetaItemList<'a> {
                 }))
             },
             |qself, path| Ok(Type::Path(TypePath { qself, path })),
-        )
+        )?;
+        self.lift_inline_matches(part_ident, context, |lift| lift.visit_type_mut(&mut ty))?;
+        Ok(ty)
+    }
+
+    pub fn lift_inline_matches_in_signature(
+        &mut self,
+        part_ident: &Option<Ident>,
+        sig: &mut Signature,
+        context: &GenericsContext,
+    ) -> Result<()> {
+        let generics = sig.generics.clone();
+        let sig_context = GenericsContext::WithGenerics(&generics, context);
+        self.lift_inline_matches(part_ident, &sig_context, |lift| {
+            lift.visit_signature_mut(sig)
+        })
+    }
+
+    // Replaces all type-level matches that are nested within types (see `INLINE_MATCH_MACRO_NAME`)
+    // with references to internal items.
+    pub fn lift_inline_matches(
+        &mut self,
+        part_ident: &Option<Ident>,
+        context: &GenericsContext,
+        visit: impl FnOnce(&mut LiftInlineMatches),
+    ) -> Result<()> {
+        let mut lift = LiftInlineMatches {
+            output: self,
+            part_ident,
+            context,
+            errors: None,
+        };
+        visit(&mut lift);
+        match lift.errors {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
     }
 
     pub fn convert_type_level_expr_const(
@@ -493,27 +1292,33 @@ impl<'a> OutputMetaItemList<'a> {
             ty.clone(),
             context,
             |ident, generics, expr, ty| {
-                if !generics.params.is_empty() {
-                    return Err(Error::new(
-                        expr.span(),
-                        "translation of this match expression would require constants with generic parameters"),
-                    );
-                }
                 Ok(TraitImplItem::Const(TraitImplItemConst {
                     attrs: attrs.clone(),
                     vis: Visibility::Inherited,
                     const_token: Default::default(),
                     ident,
+                    generics,
                     ty,
                     expr,
                 }))
             },
             |qself, path| {
-                Ok(Expr::Path(ExprPath {
-                    attrs: Vec::new(),
-                    qself,
-                    path,
-                }))
+                let mut segments = path.segments.iter().rev();
+                let item_segment = segments.next().unwrap();
+                if item_segment.arguments.is_empty() {
+                    return Ok(Expr::Path(ExprPath {
+                        attrs: Vec::new(),
+                        qself,
+                        path,
+                    }));
+                }
+                // See `create_generic_const_trait_item`.
+                let trait_segment = segments.next().unwrap();
+                let helper_ident =
+                    Self::const_helper_ident(&trait_segment.ident, &item_segment.ident);
+                let helper_args = trait_segment.arguments.clone();
+                let ty = Type::Path(TypePath { qself, path });
+                Ok(parse_quote!(<#ty as #helper_ident #helper_args>::VALUE))
             },
         )
     }
@@ -526,12 +1331,48 @@ impl<'a> OutputMetaItemList<'a> {
         context: &GenericsContext,
         sig: &Signature,
     ) -> Result<Block> {
+        // The internal function is not a method of the type that is matched on, so a receiver
+        // becomes a normal parameter.
+        let mut expr = expr;
+        let mut internal_sig = sig.clone();
+        if let (TypeLevelExpr::Match(match_expr), Some(FnArg::Receiver(receiver))) =
+            (&mut expr, internal_sig.inputs.first_mut())
+        {
+            let self_ident = Ident::new("__self", receiver.self_token.span);
+            match_expr.visit_arm_bodies_mut(&mut |expr| {
+                ReplaceSelfValue(&self_ident).visit_expr_mut(expr)
+            });
+            *internal_sig.inputs.first_mut().unwrap() = FnArg::Typed(PatType {
+                attrs: receiver.attrs.clone(),
+                pat: Box::new(Pat::Ident(PatIdent {
+                    attrs: Vec::new(),
+                    by_ref: None,
+                    mutability: receiver.mutability.filter(|_| receiver.reference.is_none()),
+                    ident: self_ident,
+                    subpat: None,
+                })),
+                colon_token: Default::default(),
+                ty: receiver.ty.clone(),
+            });
+        }
         self.convert_type_level_expr(
             part_ident,
             expr,
-            sig.clone(),
+            internal_sig,
             context,
-            |ident, generics, expr, sig| {
+            |ident, mut generics, expr, sig| {
+                // Lifetime bounds such as `'a: 'b` must be kept so that the body type-checks; they
+                // can only refer to lifetimes that are still in scope.
+                if let Some(where_clause) = &sig.generics.where_clause {
+                    for predicate in &where_clause.predicates {
+                        if let WherePredicate::Lifetime(_) = predicate {
+                            generics
+                                .make_where_clause()
+                                .predicates
+                                .push(predicate.clone());
+                        }
+                    }
+                }
                 Ok(TraitImplItem::Fn(TraitImplItemFn {
                     attrs: attrs.clone(),
                     vis: Visibility::Inherited,
@@ -548,22 +1389,34 @@ impl<'a> OutputMetaItemList<'a> {
                     &mut path.segments.last_mut().unwrap().arguments
                 {
                     path_args.colon2_token = Some(Default::default());
+                    // Lifetime arguments cannot be specified if any lifetime is late-bound, and
+                    // they can always be inferred.
+                    path_args.args = take(&mut path_args.args)
+                        .into_iter()
+                        .filter(|arg| !matches!(arg, GenericArgument::Lifetime(_)))
+                        .collect();
+                    if path_args.args.is_empty() {
+                        path.segments.last_mut().unwrap().arguments = PathArguments::None;
+                    }
                 }
+                // The forwarding function must use the same generated identifiers.
+                let mut named_sig = sig.clone();
+                name_param_patterns(&mut named_sig);
                 let mut args = Punctuated::new();
-                for arg in &sig.inputs {
-                    let FnArg::Typed(arg) = arg else {
-                        return Err(Error::new(arg.span(), "unexpected self parameter"));
-                    };
-                    let Pat::Ident(ident) = arg.pat.as_ref() else {
-                        return Err(Error::new(
-                            arg.pat.span(),
-                            "patterns in parameters are currently not supported",
-                        ));
+                for arg in &named_sig.inputs {
+                    let ident = match arg {
+                        FnArg::Receiver(receiver) => Ident::new("self", receiver.self_token.span),
+                        FnArg::Typed(arg) => {
+                            let Pat::Ident(pat_ident) = arg.pat.as_ref() else {
+                                unreachable!();
+                            };
+                            pat_ident.ident.clone()
+                        }
                     };
                     args.push(Expr::Path(ExprPath {
                         attrs: Vec::new(),
                         qself: None,
-                        path: ident.ident.clone().into(),
+                        path: ident.into(),
                     }));
                 }
                 Ok(Block {
@@ -628,17 +1481,45 @@ impl<'a> OutputMetaItemList<'a> {
             X,
         ) -> Result<TraitImplItem>,
     ) -> Result<(Option<QSelf>, Path)> {
-        let ty = match_expr.types.last().unwrap().clone();
-        let Some(match_ident) = get_type_ident(&ty) else {
-            return Err(Error::new(
-                ty.span(),
-                "type matching is currently only supported on variables",
-            ));
+        let mut match_expr = match_expr;
+        let match_type = match_expr.types.last().unwrap();
+        let ty = match_type.ty.clone();
+        let mut bounds = match_type.bounds.clone();
+        let guard_if_token = match_expr.guard_if_token;
+        let scrutinee_generics: Generics;
+        let scrutinee_context: GenericsContext;
+        let (match_ident, context) = if let Some(match_ident) = get_type_ident(&ty) {
+            (match_ident.clone(), context)
+        } else {
+            // Match on a fresh type parameter instead, which we replace with the actual type in
+            // the resulting path.
+            if bounds.is_empty() {
+                bounds = self.infer_match_bounds(&match_expr)?;
+            }
+            let match_ident = Ident::new("__Match", Span::call_site());
+            scrutinee_generics =
+                build_generics(Punctuated::from_iter([GenericParam::Type(TypeParam {
+                    attrs: Vec::new(),
+                    ident: match_ident.clone(),
+                    colon_token: None,
+                    bounds: bounds.clone(),
+                    eq_token: None,
+                    default: None,
+                })]));
+            scrutinee_context = GenericsContext::WithGenerics(&scrutinee_generics, context);
+            match_expr.types.last_mut().unwrap().ty = Type::Path(TypePath {
+                qself: None,
+                path: match_ident.clone().into(),
+            });
+            (match_ident, &scrutinee_context)
         };
         let mut expr = (match_expr, extra);
         let (match_param, mut extracted_params) =
-            isolate_type_param(&mut expr, context, match_ident)?;
-        let Some(TypeParamBound::Trait(trait_bound)) = match_param.bounds.first() else {
+            isolate_type_param(&mut expr, context, &match_ident)?;
+        if bounds.is_empty() {
+            bounds = match_param.bounds.clone();
+        }
+        let Some(TypeParamBound::Trait(trait_bound)) = bounds.first() else {
             return Err(Error::new(
                 ty.span(),
                 "no appropriate type bound for matching found",
@@ -653,6 +1534,12 @@ impl<'a> OutputMetaItemList<'a> {
         let trait_segment = trait_bound.path.segments.last().unwrap();
         let trait_ident = &trait_segment.ident;
         let trait_def_item = self.trait_def_item(trait_ident)?;
+        if trait_def_item.trait_def.extended_trait.is_some() {
+            return Err(Error::new(
+                ty.span(),
+                format!("matching on `{trait_ident}` is only supported where it is defined"),
+            ));
+        }
         Self::eliminate_exact_trait_args(
             trait_def_item,
             &trait_segment.arguments,
@@ -666,22 +1553,24 @@ impl<'a> OutputMetaItemList<'a> {
             Span::call_site(),
         );
         trait_def_item.next_internal_item_idx += 1;
-        let variants_known = trait_def_item.variants.is_some();
+        let known_variants = trait_def_item.known_variants();
         let (params, args) = extracted_params.into_iter().unzip();
-        let impl_item = create_trait_impl_item(
+        let mut impl_item = create_trait_impl_item(
             trait_item_ident.clone(),
             build_generics(params),
             TypeLevelExpr::Match(expr.0),
             expr.1,
         )?;
+        Self::mark_internal_item(&mut impl_item);
         let mut part_ident = part_ident.clone();
-        let trait_item_desc = self.create_trait_item(
+        let mut trait_item_desc = self.create_trait_item(
             &mut part_ident,
             impl_item,
             &impl_context,
             trait_def,
-            variants_known,
+            known_variants.as_deref(),
         )?;
+        trait_item_desc.internal = true;
         let trait_def_item = self.trait_def_item(trait_ident)?;
         trait_def_item.add_item(&part_ident, trait_item_desc)?;
         let mut segments = trait_bound.path.segments.clone();
@@ -689,13 +1578,27 @@ impl<'a> OutputMetaItemList<'a> {
             ident: trait_item_ident,
             arguments: build_path_arguments(args),
         });
+        // A guard type that is not known to implement the alias (e.g. the result of a type alias
+        // of another crate that is only bounded by `MetaBool`) is rejected by the compiler, which
+        // should point at the guard.
+        let span = match guard_if_token {
+            Some(_) => ty.span(),
+            None => Span::call_site(),
+        };
+        for segment in &mut segments {
+            segment.ident.set_span(span);
+            if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                args.lt_token.span = span;
+                args.gt_token.span = span;
+            }
+        }
         Ok((
             Some(QSelf {
-                lt_token: Default::default(),
+                lt_token: Token![<](span),
                 ty: Box::new(ty),
                 position: segments.len() - 1,
-                as_token: Some(Default::default()),
-                gt_token: Default::default(),
+                as_token: Some(Token![as](span)),
+                gt_token: Token![>](span),
             }),
             Path {
                 leading_colon: trait_bound.path.leading_colon.clone(),
@@ -704,6 +1607,63 @@ impl<'a> OutputMetaItemList<'a> {
         ))
     }
 
+    fn infer_match_bounds<E>(&self, match_expr: &TypeLevelExprMatch<E>) -> Result<TypeParamBounds> {
+        let variant_idents: Vec<&Ident> = match_expr
+            .arms
+            .iter()
+            .filter_map(|arm| {
+                if let Some(TypeLevelArmSelector::Specific { ident, .. }) = arm.selectors.last() {
+                    Some(ident)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if !variant_idents.is_empty() {
+            for output_item in &self.0 {
+                let OutputMetaItem::TraitDef(trait_def_item) = output_item else {
+                    continue;
+                };
+                let trait_def = trait_def_item.trait_def;
+                if !trait_def.generics.params.is_empty() {
+                    continue;
+                }
+                let matches_variants = match &trait_def.contents {
+                    TraitContents::Enum { variants } => variant_idents
+                        .iter()
+                        .all(|ident| variants.iter().any(|variant| &variant.ident == *ident)),
+                    TraitContents::Alias { .. } => {
+                        trait_def_item.known_variants().is_some_and(|variants| {
+                            variant_idents.iter().all(|ident| {
+                                variants.iter().any(|variant| &variant.ident == *ident)
+                            })
+                        })
+                    }
+                };
+                if matches_variants {
+                    let mut bounds = Punctuated::new();
+                    bounds.push(TypeParamBound::Trait(TraitBound {
+                        paren_token: None,
+                        modifier: TraitBoundModifier::None,
+                        lifetimes: None,
+                        path: trait_def.ident.clone().into(),
+                    }));
+                    return Ok(bounds);
+                }
+            }
+        }
+        if match_expr.guard_if_token.is_some() {
+            return Err(Error::new(
+                match_expr.types.last().unwrap().span(),
+                "cannot infer type bound for guard; define an alias of `enum_trait_core::meta_bool::MetaBool` within this block and use it as the bound of the guard type",
+            ));
+        }
+        Err(Error::new(
+            match_expr.types.last().unwrap().span(),
+            "cannot infer type bound for matching; specify it explicitly as in `match <T: Trait>`",
+        ))
+    }
+
     fn eliminate_exact_trait_args(
         trait_def_item: &OutputItemTraitDef,
         arguments: &PathArguments,
@@ -717,6 +1677,17 @@ impl<'a> OutputMetaItemList<'a> {
                 .iter()
                 .zip(args.args.iter())
             {
+                if let GenericArgument::Lifetime(arg_lifetime) = arg {
+                    if let Some(extracted_param_idx) =
+                        extracted_params.iter().position(|(_, extracted_arg)| {
+                            matches!(extracted_arg, GenericArgument::Lifetime(extracted_lifetime)
+                                if extracted_lifetime == arg_lifetime)
+                        })
+                    {
+                        let (extracted_param, _) = extracted_params.remove(extracted_param_idx);
+                        expr.substitute(&extracted_param, ParamSubstArg::Param(param))?;
+                    }
+                }
                 if let GenericArgument::Type(arg_ty) = arg {
                     if let Some(arg_ident) = get_type_ident(arg_ty) {
                         if let Some(extracted_param_idx) =
@@ -748,13 +1719,49 @@ impl<'a> OutputMetaItemList<'a> {
         attrs
     }
 
+    // Internal items are hidden, but not declared as private, so that the references to them that
+    // we generate in user code do not require `#[allow(deprecated)]`.
+    fn mark_internal_item(item: &mut TraitImplItem) {
+        let (attrs, vis) = match item {
+            TraitImplItem::Const(const_item) => (&mut const_item.attrs, &mut const_item.vis),
+            TraitImplItem::Type(type_item) => (&mut type_item.attrs, &mut type_item.vis),
+            TraitImplItem::Fn(fn_item) => (&mut fn_item.attrs, &mut fn_item.vis),
+        };
+        attrs.push(parse_quote!(#[doc(hidden)]));
+        *vis = Visibility::Public(Default::default());
+    }
+
+    // Checks whether a trait item belongs to the trait itself (as opposed to an item that is
+    // generated for a type-level match in a free item).
+    fn is_own_item(part_ident: &Option<Ident>) -> bool {
+        match part_ident {
+            Some(part_ident) => part_ident == SELF_TYPE_NAME,
+            None => true,
+        }
+    }
+
+    fn impl_item_attrs(attrs: Vec<Attribute>, own_item: bool) -> Vec<Attribute> {
+        let mut attrs = Self::code_item_attrs(attrs);
+        if own_item {
+            // Items of the trait may use its private items.
+            attrs.push(parse_quote!(#[allow(deprecated)]));
+        }
+        attrs
+    }
+
     pub fn code_item_attrs(mut attrs: Vec<Attribute>) -> Vec<Attribute> {
-        attrs.push(parse_quote!(#[allow(deprecated)]));
         // Function parameters are sometimes not used in all trait implementations. (It would be
         // nice to be more specific here, or to add underscores to unused parameters.)
         attrs.push(parse_quote!(#[allow(unused_variables)]));
         attrs
     }
+
+    // The implementation of a function for a single variant contains a copy of the code that is
+    // shared by all arms of a match, which may then only be partially used: locals and `mut self`
+    // receivers are sometimes only mutated or read in other arms.
+    pub fn variant_fn_lint_attr() -> Attribute {
+        parse_quote!(#[allow(unused_mut, unused_assignments)])
+    }
 }
 
 impl ToTokens for OutputMetaItemList<'_> {
@@ -773,19 +1780,63 @@ pub enum OutputMetaItem<'a> {
     Item(Item),
 }
 
+pub struct LiftInlineMatches<'a, 'b, 'c> {
+    output: &'a mut OutputMetaItemList<'b>,
+    part_ident: &'a Option<Ident>,
+    context: &'a GenericsContext<'c>,
+    errors: Option<Error>,
+}
+
+impl LiftInlineMatches<'_, '_, '_> {
+    fn lift(&mut self, mac: &Macro) -> Result<Type> {
+        let match_expr: TypeLevelExprMatch<TypeLevelExpr<Type>> = mac.parse_body()?;
+        self.output.convert_type_level_expr_type(
+            &Vec::new(),
+            self.part_ident,
+            TypeLevelExpr::Match(match_expr),
+            self.context,
+            &Punctuated::new(),
+        )
+    }
+}
+
+impl VisitMut for LiftInlineMatches<'_, '_, '_> {
+    fn visit_type_mut(&mut self, i: &mut Type) {
+        if let Type::Macro(TypeMacro { mac }) = i {
+            if mac.path.is_ident(INLINE_MATCH_MACRO_NAME) {
+                match self.lift(mac) {
+                    Ok(ty) => *i = ty,
+                    Err(error) => add_error(&mut self.errors, error),
+                }
+                return;
+            }
+        }
+        visit_mut::visit_type_mut(self, i)
+    }
+}
+
 pub struct OutputTraitItemDesc {
     item: TraitItem,
     variants: Option<(Vec<(Option<ImplVariant>, ImplItem)>, Span)>,
     dependent_idents: Vec<Ident>,
+    // Whether the item was marked by `mark_internal_item`.
+    internal: bool,
+    warnings: Vec<OutputWarning>,
 }
 
 pub struct OutputItemTraitDef<'a> {
     pub trait_def: &'a ItemTraitDef,
     pub extracted_generics: Generics,
     pub variants: Option<Vec<OutputImplVariant>>,
+    // For aliases whose implementation is forwarded to the aliased trait, the variants of that
+    // trait in the order expected by its implementation macro, if known.
+    pub variant_order: Option<Vec<Ident>>,
     pub impl_items: ImplPartList<TraitItem>,
     pub dependent_idents: Vec<Ident>,
     pub next_internal_item_idx: usize,
+    // For open enum traits, the items that are implemented for each variant.
+    pub variant_defaults: Vec<VariantDefault>,
+    pub warnings: Vec<OutputWarning>,
 }
 
 impl<'a> OutputItemTraitDef<'a> {
@@ -794,96 +1845,168 @@ impl<'a> OutputItemTraitDef<'a> {
         part_ident: &Option<Ident>,
         item: OutputTraitItemDesc,
     ) -> Result<()> {
+        let item_ident = trait_item_ident(&item.item).cloned();
+        let trait_item = item.item.clone();
         self.impl_items.add_item(part_ident, item.item);
 
-        if let Some((variants, variants_span)) = item.variants {
-            if self.variants.is_none() {
-                self.variants = Some(
-                    variants
-                        .iter()
-                        .map(|(orig_variant, _)| {
-                            // `unwrap` because `create_trait_item` only outputs `None` if
-                            // `variants_known` is `true`.
-                            let orig_variant = orig_variant.as_ref().unwrap();
-                            let mut variant = orig_variant.clone();
-                            add_underscores_to_all_params(&mut variant.impl_generics)?;
-                            variant.trait_args.substitute_all_params(
-                                &orig_variant.impl_generics,
-                                &variant.impl_generics,
-                            )?;
-                            variant.variant.generics.substitute_all_params(
-                                &orig_variant.impl_generics,
-                                &variant.impl_generics,
-                            )?;
-                            add_underscores_to_all_params(&mut variant.variant.generics)?;
-                            Ok(OutputImplVariant {
-                                variant,
-                                impl_items: ImplPartList::new(),
-                            })
-                        })
-                        .collect::<Result<_>>()?,
-                );
+        if let Some((mut variants, variants_span)) = item.variants {
+            for variant in variants
+                .iter_mut()
+                .filter_map(|(variant, _)| variant.as_mut())
+            {
+                self.trait_def
+                    .replace_aliased_trait_bounds(&mut variant.variant.generics);
             }
-            let existing_variants = self.variants.as_mut().unwrap();
-            let mut variant_iter = variants.into_iter();
-            for existing_variant in existing_variants {
-                let existing_trait_variant = &existing_variant.variant.variant;
-                let Some((variant, mut impl_item)) = variant_iter.next() else {
-                    return Err(Error::new(
-                        variants_span,
-                        format!(
-                            "too few variants; expected `{}`",
-                            &existing_trait_variant.ident
-                        ),
-                    ));
-                };
-                if let Some(variant) = variant {
-                    let trait_variant = &variant.variant;
-                    if existing_trait_variant.ident != trait_variant.ident {
-                        return Err(Error::new(
-                            trait_variant.ident.span(),
-                            format!("expected variant `{}`", &existing_trait_variant.ident),
-                        ));
+            let default_impl_item = if let [(None, impl_item)] = variants.as_slice() {
+                Some(impl_item.clone())
+            } else {
+                variants
+                    .iter()
+                    .position(|(impl_variant, _)| impl_variant.is_none())
+                    .map(|default_idx| variants.remove(default_idx).1)
+            };
+            if self.trait_def.open {
+                if let Some(item_ident) = item_ident {
+                    let defaults_trait_items = match &default_impl_item {
+                        Some(impl_item) => Self::defaults_trait_items(&trait_item, impl_item)?,
+                        None => None,
+                    };
+                    self.variant_defaults.push(VariantDefault {
+                        item_ident,
+                        impl_item: default_impl_item,
+                        defaults_trait_items,
+                        internal: item.internal,
+                    });
+                }
+            }
+            if self.variants.is_none() {
+                let mut new_variants: Vec<OutputImplVariant> = Vec::new();
+                for (orig_variant, _) in &variants {
+                    // `unwrap` because `create_trait_item` only outputs `None` if
+                    // `known_variants` is `Some`.
+                    let orig_variant = orig_variant.as_ref().unwrap();
+                    if new_variants.iter().any(|new_variant| {
+                        Self::variant_matches(&new_variant.variant, orig_variant)
+                    }) {
+                        // Reported as unreachable below.
+                        continue;
                     }
-                    let mut expected_impl_generics = existing_variant.variant.impl_generics.clone();
-                    rename_all_params(&mut expected_impl_generics, &variant.impl_generics)?;
-                    check_token_equality(&variant.impl_generics, &expected_impl_generics)?;
-                    let mut variant_generics = trait_variant.generics.clone();
-                    variant_generics.substitute_all_params(
+                    let mut variant = orig_variant.clone();
+                    add_underscores_to_non_lifetime_params(&mut variant.impl_generics)?;
+                    variant.trait_args.substitute_all_params(
+                        &orig_variant.impl_generics,
                         &variant.impl_generics,
-                        &existing_variant.variant.impl_generics,
                     )?;
-                    impl_item.substitute_all_params(
+                    variant.variant.generics.substitute_all_params(
+                        &orig_variant.impl_generics,
                         &variant.impl_generics,
-                        &existing_variant.variant.impl_generics,
                     )?;
-                    let mut expected_trait_args = existing_variant.variant.trait_args.clone();
-                    expected_trait_args.substitute_all_params(
-                        &existing_variant.variant.impl_generics,
-                        &variant.impl_generics,
-                    )?;
-                    check_token_equality(&variant.trait_args, &expected_trait_args)?;
-                    let mut expected_generics = existing_trait_variant.generics.clone();
-                    rename_all_params(&mut expected_generics, &variant_generics)?;
-                    check_token_equality(&variant_generics, &expected_generics)?;
-                    impl_item.substitute_all_params(
-                        &variant_generics,
-                        &existing_trait_variant.generics,
-                    )?;
-                } else {
-                    assert!(variant_iter.next().is_none());
-                    variant_iter = vec![(None, impl_item.clone())].into_iter();
+                    add_underscores_to_all_params(&mut variant.variant.generics)?;
+                    new_variants.push(OutputImplVariant {
+                        variant,
+                        impl_items: ImplPartList::new(),
+                    });
+                }
+                if let Some(variant_order) = &self.variant_order {
+                    self.check_variant_order(&new_variants, variant_order, variants_span)?;
+                    new_variants.sort_by_key(|new_variant| {
+                        variant_order
+                            .iter()
+                            .position(|ident| ident == &new_variant.variant.variant.ident)
+                    });
                 }
-                existing_variant.impl_items.add_item(part_ident, impl_item);
+                self.variants = Some(new_variants);
             }
-            if let Some((Some(variant), _)) = variant_iter.next() {
-                return Err(Error::new(
-                    variant.variant.ident.span(),
-                    "superfluous variant",
-                ));
+            let existing_variants = self.variants.as_mut().unwrap();
+            if let [(None, impl_item)] = variants.as_slice() {
+                for existing_variant in existing_variants {
+                    existing_variant
+                        .impl_items
+                        .add_item(part_ident, impl_item.clone());
+                }
+            } else {
+                // Assign the implementations to the variants independently of their order, and
+                // collect as many errors as possible.
+                let mut variants: Vec<_> = variants
+                    .into_iter()
+                    .map(|(variant, impl_item)| Some((variant.unwrap(), impl_item)))
+                    .collect();
+                let mut errors = None;
+                let mut missing_variants = Vec::new();
+                for existing_variant in existing_variants.iter_mut() {
+                    let Some((variant, impl_item)) = variants
+                        .iter_mut()
+                        .find(|entry| {
+                            matches!(entry, Some((variant, _))
+                                if Self::variant_matches(&existing_variant.variant, variant))
+                        })
+                        .and_then(Option::take)
+                    else {
+                        missing_variants.push(Self::variant_description(
+                            self.trait_def,
+                            &existing_variant.variant,
+                        ));
+                        continue;
+                    };
+                    match Self::adapt_variant_impl_item(
+                        &existing_variant.variant,
+                        variant,
+                        impl_item,
+                    ) {
+                        Ok(impl_item) => {
+                            existing_variant.impl_items.add_item(part_ident, impl_item)
+                        }
+                        Err(error) => add_error(&mut errors, error),
+                    }
+                }
+                for (variant, _) in variants.into_iter().flatten() {
+                    let ident = &variant.variant.ident;
+                    if existing_variants.iter().any(|existing_variant| {
+                        Self::variant_matches(&existing_variant.variant, &variant)
+                    }) {
+                        self.warnings
+                            .push(OutputWarning::new(ident.span(), "unreachable arm"));
+                    } else if existing_variants
+                        .iter()
+                        .any(|existing_variant| &existing_variant.variant.variant.ident == ident)
+                    {
+                        add_error(
+                            &mut errors,
+                            Error::new(
+                                ident.span(),
+                                format!("trait arguments do not match any implementation of variant `{ident}`"),
+                            ),
+                        );
+                    } else {
+                        add_error(
+                            &mut errors,
+                            Error::new(
+                                ident.span(),
+                                format!(
+                                    "`{ident}` is not a variant of `{}`",
+                                    &self.trait_def.ident
+                                ),
+                            ),
+                        );
+                    }
+                }
+                if !missing_variants.is_empty() {
+                    add_error(
+                        &mut errors,
+                        Error::new(
+                            variants_span,
+                            format!("missing variant(s): {}", missing_variants.join(", ")),
+                        ),
+                    );
+                }
+                if let Some(errors) = errors {
+                    return Err(errors);
+                }
             }
         }
 
+        self.warnings.extend(item.warnings);
+
         for ident in item.dependent_idents {
             if !self.dependent_idents.contains(&ident) {
                 self.dependent_idents.push(ident);
@@ -893,6 +2016,133 @@ impl<'a> OutputItemTraitDef<'a> {
         Ok(())
     }
 
+    // Checks that the variants determined by the first `match` expression of an alias correspond
+    // to those of the aliased trait, as the implementation macro of that trait would otherwise
+    // fail to match them.
+    fn check_variant_order(
+        &self,
+        variants: &[OutputImplVariant],
+        variant_order: &[Ident],
+        variants_span: Span,
+    ) -> Result<()> {
+        let mut errors = None;
+        for output_variant in variants {
+            let ident = &output_variant.variant.variant.ident;
+            if !variant_order.contains(ident) {
+                add_error(
+                    &mut errors,
+                    Error::new(
+                        ident.span(),
+                        format!("`{ident}` is not a variant of `{}`", &self.trait_def.ident),
+                    ),
+                );
+            }
+        }
+        let missing_variants: Vec<String> = variant_order
+            .iter()
+            .filter(|ident| {
+                !variants
+                    .iter()
+                    .any(|output_variant| &output_variant.variant.variant.ident == *ident)
+            })
+            .map(|ident| format!("`{ident}`"))
+            .collect();
+        if !missing_variants.is_empty() {
+            add_error(
+                &mut errors,
+                Error::new(
+                    variants_span,
+                    format!("missing variant(s): {}", missing_variants.join(", ")),
+                ),
+            );
+        }
+        match errors {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+
+    fn variant_matches(existing_variant: &ImplVariant, variant: &ImplVariant) -> bool {
+        if existing_variant.variant.ident != variant.variant.ident {
+            return false;
+        }
+        let check_trait_args = || {
+            let mut expected_impl_generics = existing_variant.impl_generics.clone();
+            rename_all_params(&mut expected_impl_generics, &variant.impl_generics)?;
+            let mut expected_trait_args = existing_variant.trait_args.clone();
+            expected_trait_args
+                .substitute_all_params(&existing_variant.impl_generics, &expected_impl_generics)?;
+            check_token_equality(&variant.trait_args, &expected_trait_args)
+        };
+        check_trait_args().is_ok()
+    }
+
+    fn adapt_variant_impl_item(
+        existing_variant: &ImplVariant,
+        variant: ImplVariant,
+        mut impl_item: ImplItem,
+    ) -> Result<ImplItem> {
+        let existing_trait_variant = &existing_variant.variant;
+        let trait_variant = &variant.variant;
+        let mut expected_impl_generics = existing_variant.impl_generics.clone();
+        rename_all_params(&mut expected_impl_generics, &variant.impl_generics)?;
+        check_token_equality(&variant.impl_generics, &expected_impl_generics)?;
+        let mut variant_generics = trait_variant.generics.clone();
+        variant_generics
+            .substitute_all_params(&variant.impl_generics, &existing_variant.impl_generics)?;
+        impl_item.substitute_all_params(&variant.impl_generics, &existing_variant.impl_generics)?;
+        let mut expected_generics = existing_trait_variant.generics.clone();
+        rename_all_params(&mut expected_generics, &variant_generics)?;
+        check_token_equality(&variant_generics, &expected_generics)?;
+        impl_item.substitute_all_params(&variant_generics, &existing_trait_variant.generics)?;
+        Ok(impl_item)
+    }
+
+    // Describes a variant in the way it would be written as a selector in a `match` arm,
+    // preferring the parameter names of the original definition if available.
+    fn variant_description(trait_def: &ItemTraitDef, variant: &ImplVariant) -> String {
+        let mut trait_variant = &variant.variant;
+        if let TraitContents::Enum { variants } = &trait_def.contents {
+            if let Some(orig_variant) = variants
+                .iter()
+                .find(|orig_variant| orig_variant.ident == trait_variant.ident)
+            {
+                trait_variant = orig_variant;
+            }
+        }
+        let ident = &trait_variant.ident;
+        let generics = &trait_variant.generics;
+        let mut description = format!("`{}`", tokens_to_string(quote!(#ident #generics)));
+        let trait_args = &variant.trait_args;
+        if trait_args.to_token_stream().to_string()
+            != generic_args(&variant.impl_generics)
+                .to_token_stream()
+                .to_string()
+        {
+            let trait_ident = &trait_def.ident;
+            description += &format!(
+                " for `{}`",
+                tokens_to_string(quote!(#trait_ident #trait_args))
+            );
+        }
+        description
+    }
+
+    pub fn known_variants(&self) -> Option<Vec<TraitVariant>> {
+        let variants = self.variants.as_ref()?;
+        let mut known_variants: Vec<TraitVariant> = Vec::new();
+        for output_variant in variants {
+            let variant = &output_variant.variant.variant;
+            if !known_variants
+                .iter()
+                .any(|known_variant| known_variant.ident == variant.ident)
+            {
+                known_variants.push(variant.clone());
+            }
+        }
+        Some(known_variants)
+    }
+
     pub fn impl_context(&self) -> GenericsContext<'a> {
         let mut bounds = Punctuated::new();
         bounds.push(TypeParamBound::Trait(TraitBound {
@@ -919,6 +2169,330 @@ impl<'a> OutputItemTraitDef<'a> {
         ident_with_prefix(ident, "__trait_impl_body__", false)
     }
 
+    pub fn variants_macro_ident(ident: &Ident) -> Ident {
+        ident_with_prefix(ident, "__trait_variants__", false)
+    }
+
+    fn sealed_module_ident(ident: &Ident) -> Ident {
+        ident_with_prefix(ident, "__sealed__", false)
+    }
+
+    fn where_module_ident(ident: &Ident) -> Ident {
+        ident_with_prefix(ident, "__where__", false)
+    }
+
+    fn type_eq_predicates(&self) -> Vec<&TypeEqPredicate> {
+        let Some(where_clause) = &self.trait_def.generics.where_clause else {
+            return Vec::new();
+        };
+        where_clause
+            .predicates
+            .iter()
+            .filter_map(|predicate| match predicate {
+                MetaWherePredicate::TypeEq(type_eq_predicate) => Some(type_eq_predicate),
+                MetaWherePredicate::Predicate(_) => None,
+            })
+            .collect()
+    }
+
+    // Checks whether any param is bounded by the trait itself, as in `Succ<P: MetaNumLessThan<O>>`.
+    // Then the type equalities of a `where` clause are only satisfied inductively, which Rust
+    // cannot verify, so we have to trust the user in that case.
+    fn has_recursive_bound(&self, generics: &Generics) -> bool {
+        generics.type_params().any(|type_param| {
+            type_param.bounds.iter().any(|bound| {
+                matches!(bound, TypeParamBound::Trait(trait_bound)
+                if trait_bound.path.segments.last().is_some_and(|segment| {
+                    segment.ident == self.trait_def.ident
+                }))
+            })
+        })
+    }
+
+    // Outputs the macro that passes the variants of the trait to a `meta!` block containing an
+    // alias of the trait, so that the alias can implement them in the order expected by our
+    // implementation macro. Its arguments are the alias and the contents of the block, which is
+    // then processed again with an additional `__variants` attribute.
+    fn output_variants_macro(&self, tokens: &mut TokenStream) {
+        let variants_macro_ident = Self::variants_macro_ident(&self.trait_def.ident);
+        let alias_param = quote!($_Alias);
+        let variants = match &self.trait_def.contents {
+            TraitContents::Enum { variants } => Some(variants.iter().cloned().collect()),
+            TraitContents::Alias { .. } => self.known_variants(),
+        };
+        let variants_attr_args = match variants {
+            Some(variants) => {
+                let variants = variants.iter().map(|variant| {
+                    let ident = &variant.ident;
+                    let generics = replace_tokens(
+                        variant.generics.to_token_stream(),
+                        &self.trait_def.ident,
+                        &alias_param,
+                    );
+                    quote!(#ident #generics)
+                });
+                quote!(#alias_param = #(#variants),*)
+            }
+            None => alias_param.clone(),
+        };
+        tokens.extend(quote! {
+            #[macro_export]
+            macro_rules! #variants_macro_ident {
+                (#alias_param:ident, { $($_Input:tt)* }) => {
+                    ::enum_trait::meta! {
+                        #![__variants(#variants_attr_args)]
+                        $($_Input)*
+                    }
+                };
+            }
+            pub use #variants_macro_ident;
+        });
+    }
+
+    fn extend_macro_ident(ident: &Ident) -> Ident {
+        ident_with_prefix(ident, "__trait_extend__", false)
+    }
+
+    fn defaults_trait_ident(ident: &Ident) -> Ident {
+        ident_with_prefix(ident, "__defaults__", false)
+    }
+
+    // Returns the error to report when trying to extend the trait, or `None` if it can be
+    // extended.
+    fn extension_error(&self) -> Option<String> {
+        let trait_ident = &self.trait_def.ident;
+        if !matches!(self.trait_def.contents, TraitContents::Enum { .. }) {
+            Some(format!(
+                "`{trait_ident}` is a trait alias; only enum traits can be extended"
+            ))
+        } else if !self.trait_def.open {
+            Some(format!(
+                "`{trait_ident}` is sealed; mark it with `#[open]` to allow extensions"
+            ))
+        } else if !self.trait_def.generics.params.is_empty() {
+            Some(format!(
+                "`{trait_ident}` has parameters; extending generic enum traits is not supported"
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn variant_default(&self, item_ident: &Ident) -> Option<&ImplItem> {
+        if self.extension_error().is_some() {
+            return None;
+        }
+        self.variant_defaults
+            .iter()
+            .find(|variant_default| &variant_default.item_ident == item_ident)
+            .and_then(|variant_default| variant_default.impl_item.as_ref())
+    }
+
+    // Adds the implementation for variants that are added by extensions as the default of a
+    // function or constant in the trait declaration, so that it is compiled within this crate.
+    // Associated types cannot have defaults; see `output_defaults_trait`.
+    fn trait_item_with_default<'b>(&self, item: &'b TraitItem) -> Cow<'b, TraitItem> {
+        let Some(default_impl_item) =
+            trait_item_ident(item).and_then(|ident| self.variant_default(ident))
+        else {
+            return Cow::Borrowed(item);
+        };
+        match (item, default_impl_item) {
+            (TraitItem::Fn(fn_item), ImplItem::Fn(default_fn_item)) => {
+                let mut fn_item = fn_item.clone();
+                fn_item.attrs = OutputMetaItemList::code_item_attrs(take(&mut fn_item.attrs));
+                fn_item
+                    .attrs
+                    .push(OutputMetaItemList::variant_fn_lint_attr());
+                // The declaration does not use parameter patterns, but the body may.
+                fn_item.sig = default_fn_item.sig.clone();
+                fn_item.default = Some(default_fn_item.block.clone());
+                fn_item.semi_token = None;
+                Cow::Owned(TraitItem::Fn(fn_item))
+            }
+            (TraitItem::Const(const_item), ImplItem::Const(default_const_item)) => {
+                let mut const_item = const_item.clone();
+                const_item.default =
+                    Some((default_const_item.eq_token, default_const_item.expr.clone()));
+                Cow::Owned(TraitItem::Const(const_item))
+            }
+            _ => Cow::Borrowed(item),
+        }
+    }
+
+    // Converts an associated type and its implementation for variants that are added by
+    // extensions to items of the trait output by `output_defaults_trait`, where `Self` is replaced
+    // by a parameter.
+    fn defaults_trait_items(
+        item: &TraitItem,
+        default_impl_item: &ImplItem,
+    ) -> Result<Option<(TraitItemType, ImplItem)>> {
+        let TraitItem::Type(type_item) = item else {
+            return Ok(None);
+        };
+        let self_param = self_type_param(None, Punctuated::new());
+        let self_arg = GenericArgument::Type(parse_quote!(__Self));
+        let self_subst = ParamSubstArg::Arg(&self_arg);
+        let mut decl_item = type_item.clone();
+        decl_item.attrs = Vec::new();
+        decl_item.default = None;
+        decl_item.generics.substitute(&self_param, self_subst)?;
+        decl_item.bounds.substitute(&self_param, self_subst)?;
+        let mut impl_item = default_impl_item.clone();
+        impl_item.substitute(&self_param, self_subst)?;
+        Ok(Some((decl_item, impl_item)))
+    }
+
+    // Outputs a trait that is implemented for `()` and contains the implementations of associated
+    // types for variants that are added by extensions. The macro output by `output_extend_macro`
+    // refers to these via the path to the trait's module, so that they are compiled within this
+    // crate.
+    fn output_defaults_trait(&self, tokens: &mut TokenStream) {
+        if self.extension_error().is_some() {
+            return;
+        }
+        let (decl_items, impl_items): (Vec<_>, Vec<_>) = self
+            .variant_defaults
+            .iter()
+            .filter_map(|variant_default| variant_default.defaults_trait_items.as_ref())
+            .cloned()
+            .unzip();
+        if decl_items.is_empty() {
+            return;
+        }
+        let trait_ident = &self.trait_def.ident;
+        let vis = &self.trait_def.vis;
+        let defaults_trait_ident = Self::defaults_trait_ident(trait_ident);
+        tokens.extend(quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            #vis trait #defaults_trait_ident<__Self: #trait_ident> {
+                #(#decl_items)*
+            }
+
+            impl<__Self: #trait_ident> #defaults_trait_ident<__Self> for () {
+                #(#impl_items)*
+            }
+        });
+    }
+
+    // Outputs the macro that implements the trait for a variant added by `extend enum trait`. Its
+    // arguments are the path to the trait's module, the variant, and the items that the extension
+    // implements itself. All other items that are implemented per variant are taken from the `_`
+    // arms of their `match` expressions, which are compiled within this crate: functions and
+    // constants via their defaults in the trait declaration, and types via the trait output by
+    // `output_defaults_trait`.
+    fn output_extend_macro(&self, tokens: &mut TokenStream) {
+        let trait_ident = &self.trait_def.ident;
+        let extend_macro_ident = Self::extend_macro_ident(trait_ident);
+        let defaults_trait_ident = Self::defaults_trait_ident(trait_ident);
+        let ref_path_param_ident = Ident::new("_ref_path", Span::call_site());
+        let ref_path_param = quote!($#ref_path_param_ident);
+        let mut macro_contents = TokenStream::new();
+        if let Some(error) = self.extension_error() {
+            macro_contents.extend(quote!(
+                ($($_Args:tt)*) => {
+                    ::core::compile_error!(#error);
+                };
+            ));
+        } else {
+            // Each item is output by a separate invocation, which checks whether the item is
+            // among the ones implemented by the extension.
+            let mut item_invocations = TokenStream::new();
+            for variant_default in &self.variant_defaults {
+                let item_ident = &variant_default.item_ident;
+                if let Some(impl_item) = &variant_default.impl_item {
+                    let ImplItem::Type(type_item) = impl_item else {
+                        // Functions and constants have a default in the trait declaration.
+                        continue;
+                    };
+                    let generics = &type_item.generics;
+                    let args = generic_args(generics);
+                    let where_clause = &generics.where_clause;
+                    macro_contents.extend(quote!(
+                        (@default #item_ident, $(#ref_path_param:ident::)*, [#item_ident $($_Rest:ident)*]) => {};
+                        (@default #item_ident, $(#ref_path_param:ident::)*, [$_Other:ident $($_Rest:ident)*]) => {
+                            $(#ref_path_param::)*#extend_macro_ident!(@default #item_ident, $(#ref_path_param::)*, [$($_Rest)*]);
+                        };
+                        (@default #item_ident, $(#ref_path_param:ident::)*, []) => {
+                            type #item_ident #generics = <() as $(#ref_path_param::)*#defaults_trait_ident<Self>>::#item_ident #args #where_clause;
+                        };
+                    ));
+                } else if variant_default.internal {
+                    // Internal items cannot be implemented by the extension. (Public items
+                    // without a default are reported as missing by the compiler.)
+                    let error = format!(
+                        "cannot extend `{trait_ident}`: a type-level `match` on `{trait_ident}` outside of `trait impl {trait_ident}` has no `_` arm"
+                    );
+                    macro_contents.extend(quote!(
+                        (@default #item_ident, $($_Args:tt)*) => {
+                            ::core::compile_error!(#error);
+                        };
+                    ));
+                } else {
+                    continue;
+                }
+                item_invocations.extend(quote!(
+                    $(#ref_path_param::)*#extend_macro_ident!(@default #item_ident, $(#ref_path_param::)*, [$($_Provided)*]);
+                ));
+            }
+            macro_contents.extend(quote!(
+                (
+                    $(#ref_path_param:ident::)*,
+                    [$($_Generics:tt)*] $_Variant:ty,
+                    [$($_Provided:ident)*] { $($_Body:tt)* }
+                ) => {
+                    impl<$($_Generics)*> $(#ref_path_param::)*#trait_ident for $_Variant {
+                        #item_invocations
+                        $($_Body)*
+                    }
+                };
+            ));
+        }
+        tokens.extend(quote! {
+            #[macro_export]
+            macro_rules! #extend_macro_ident {
+                #macro_contents
+            }
+            pub use #extend_macro_ident;
+        });
+    }
+
+    // Outputs the variants of `extend enum trait`, and implements the extended trait for them via
+    // the macro output by `output_extend_macro`.
+    fn output_extension(&self, extended_trait: &Path, tokens: &mut TokenStream) {
+        self.output_contents(tokens);
+
+        let mut extend_macro_path = extended_trait.clone();
+        let last_segment = extend_macro_path.segments.last_mut().unwrap();
+        last_segment.ident = Self::extend_macro_ident(&last_segment.ident);
+        let mut ref_path = TokenStream::new();
+        Self::output_ref_path(extended_trait, &mut ref_path);
+        for output_variant in self.variants.iter().flatten() {
+            let variant = &output_variant.variant.variant;
+            let variant_ident = &variant.ident;
+            let variant_params = &variant.generics.params;
+            let variant_args = generic_args(&variant.generics);
+            let mut provided_idents = Vec::new();
+            let mut impl_items = TokenStream::new();
+            for part in &output_variant.impl_items.0 {
+                for impl_item in &part.items {
+                    provided_idents.extend(impl_item_ident(impl_item));
+                    impl_item.to_tokens(&mut impl_items);
+                }
+            }
+            tokens.extend(quote!(
+                #extend_macro_path!(
+                    #ref_path,
+                    [#variant_params] #variant_ident #variant_args,
+                    [#(#provided_idents)*] { #impl_items }
+                );
+            ));
+        }
+
+        tokens.append_all(&self.warnings);
+    }
+
     fn output_contents(&self, tokens: &mut TokenStream) {
         if let TraitContents::Enum { variants } = &self.trait_def.contents {
             for variant in variants {
@@ -934,6 +2508,19 @@ impl<'a> OutputItemTraitDef<'a> {
         self.trait_def
             .generics
             .erase_in_generics(&mut variant_generics);
+        // Lifetime params of the trait are not params of the variant type, so bounds that refer
+        // to them are only checked by the trait impls.
+        for param in &mut variant_generics.params {
+            if let GenericParam::Type(type_param) = param {
+                type_param.bounds = take(&mut type_param.bounds)
+                    .into_iter()
+                    .filter(|bound| !self.references_lifetime_param(bound))
+                    .collect();
+                if type_param.bounds.is_empty() {
+                    type_param.colon_token = None;
+                }
+            }
+        }
 
         let phantom_types = phantom_types(&variant_generics);
         let struct_item = ItemStruct {
@@ -951,6 +2538,29 @@ impl<'a> OutputItemTraitDef<'a> {
         struct_item.to_tokens(tokens);
 
         Self::output_variant_dummy_impls(&variant.ident, &variant_generics, tokens);
+
+        // Extensions can only be added to open traits.
+        if !self.trait_def.open && self.trait_def.extended_trait.is_none() {
+            let sealed_module_ident = Self::sealed_module_ident(&self.trait_def.ident);
+            Self::output_variant_dummy_impl(
+                &variant.ident,
+                &variant_generics,
+                &generic_args(&variant_generics),
+                quote!(#sealed_module_ident::Sealed),
+                TokenStream::new(),
+                tokens,
+            );
+        }
+    }
+
+    fn references_lifetime_param(&self, bound: &TypeParamBound) -> bool {
+        let mut bounds = TypeParamBounds::new();
+        bounds.push(bound.clone());
+        self.extracted_generics.lifetimes().any(|lifetime_param| {
+            bounds
+                .references_param(&GenericParam::Lifetime(lifetime_param.clone()))
+                .unwrap_or(true)
+        })
     }
 
     fn output_variant_dummy_impls(
@@ -1200,6 +2810,11 @@ impl<'a> OutputItemTraitDef<'a> {
 
 impl ToTokens for OutputItemTraitDef<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        if let Some(extended_trait) = &self.trait_def.extended_trait {
+            self.output_extension(extended_trait, tokens);
+            return;
+        }
+
         // Specifies whether this trait has independent `impl` items, as opposed to having them
         // defined automatically via the implementation macro of the trait we are aliasing.
         let independent_impls = match &self.trait_def.contents {
@@ -1224,6 +2839,15 @@ impl ToTokens for OutputItemTraitDef<'_> {
         ]);
         let mut macro_default_type_bound_params = TokenStream::new();
         let mut macro_default_type_bound_args = TokenStream::new();
+        // Lifetime params are passed to the macros as well, as the bounds of type bound params and
+        // the items of aliases may refer to them under different names.
+        for lifetime_param in self.extracted_generics.lifetimes() {
+            let lifetime = &lifetime_param.lifetime;
+            let ident = &lifetime.ident;
+            macro_default_type_bound_params.extend(quote!($#ident:lifetime, ));
+            macro_default_type_bound_args.extend(quote!($#ident, ));
+            macro_args_base.insert(ident.clone(), MacroArg::single(lifetime));
+        }
         for trait_param in &self.trait_def.generics.params {
             if let MetaGenericParam::TypeBound(type_bound_param) = trait_param {
                 let ident = &type_bound_param.ident;
@@ -1245,6 +2869,14 @@ impl ToTokens for OutputItemTraitDef<'_> {
         }
 
         let generalize = |mut tokens| {
+            for lifetime_param in self.extracted_generics.lifetimes() {
+                let ident = &lifetime_param.lifetime.ident;
+                tokens = replace_param_with_tokens(
+                    tokens,
+                    &GenericParam::Lifetime(lifetime_param.clone()),
+                    &quote!($#ident),
+                );
+            }
             for trait_param in &self.trait_def.generics.params {
                 if let MetaGenericParam::TypeBound(type_bound_param) = trait_param {
                     let ident = &type_bound_param.ident;
@@ -1259,6 +2891,8 @@ impl ToTokens for OutputItemTraitDef<'_> {
 
         let mut macro_parent_type_bound_args = macro_default_type_bound_args;
         if let TraitContents::Alias { path } = &self.trait_def.contents {
+            // The lifetime args of the aliased trait are checked to coincide with our lifetime
+            // params, so we can pass them on in the same way as type bound args.
             for arg in &path.arguments.args {
                 if let MetaGenericArgument::TraitAlias(alias_arg) = arg {
                     let ident = &alias_arg.ident;
@@ -1289,11 +2923,18 @@ impl ToTokens for OutputItemTraitDef<'_> {
                     }
                 }
             }
+            // Defaults for extensions are only added to the declaration of the trait itself, as
+            // they may refer to names that are not in scope where the macro is expanded.
+            let mut trait_body = macro_body.clone();
             let mut impl_items = TokenStream::new();
+            let mut trait_body_items = TokenStream::new();
             for impl_item in &part.items {
                 impl_item.to_tokens(&mut impl_items);
+                self.trait_item_with_default(impl_item)
+                    .to_tokens(&mut trait_body_items);
             }
             macro_body.extend(generalize(impl_items));
+            trait_body.extend(generalize(trait_body_items));
             if let Some(part_ident) = &part.ident {
                 macro_contents.extend(quote!(
                     (#part_ident, #macro_params_base) => { #macro_body };
@@ -1304,7 +2945,7 @@ impl ToTokens for OutputItemTraitDef<'_> {
             // currently doesn't seem to expand such invocations transparently enough, so that IDE
             // navigation fails. Therefore, we expand the macro ourselves, essentially duplicating
             // its contents in our output.
-            let expanded_macro = expand_macro_body(macro_body, &macro_args_base);
+            let expanded_macro = expand_macro_body(trait_body, &macro_args_base);
             trait_items.push(TraitItem::Verbatim(expanded_macro));
         }
         tokens.extend(quote! {
@@ -1347,6 +2988,20 @@ impl ToTokens for OutputItemTraitDef<'_> {
                 }) {
                     supertraits.push(TypeParamBound::Lifetime(parse_quote!('static)));
                 }
+                // The match expressions in trait impls assume that the variants are the only
+                // types implementing the trait, so by default, we prevent other crates from
+                // implementing it via a supertrait that cannot be named outside of this crate.
+                if !self.trait_def.open {
+                    let sealed_module_ident = Self::sealed_module_ident(trait_ident);
+                    tokens.extend(quote! {
+                        #[doc(hidden)]
+                        #[allow(non_snake_case)]
+                        mod #sealed_module_ident {
+                            pub trait Sealed {}
+                        }
+                    });
+                    supertraits.push(parse_quote!(#sealed_module_ident::Sealed));
+                }
             }
             TraitContents::Alias { path } => {
                 let mut path = path.extract_path();
@@ -1361,8 +3016,56 @@ impl ToTokens for OutputItemTraitDef<'_> {
                 }));
             }
         }
+        // Type equalities in the `where` clause cannot be expressed as bounds of the trait, as
+        // they would not be implied by it. Instead, we add them to the `where` clauses of the
+        // variant impls, via a trait that is only implemented for equal types. If this does not
+        // hold for a variant, its impl cannot be used, or is rejected right away if it does not
+        // have any parameters.
+        let type_eq_predicates = self.type_eq_predicates();
+        let mut attrs = self.trait_def.attrs.clone();
+        if !type_eq_predicates.is_empty() {
+            let predicates = type_eq_predicates
+                .iter()
+                .map(|predicate| tokens_to_string(predicate.to_token_stream()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let predicates = predicates.replace('{', "{{").replace('}', "}}");
+            // Type and const arguments can be included in the message, but lifetimes cannot.
+            let trait_args: Vec<String> = self
+                .extracted_generics
+                .params
+                .iter()
+                .filter_map(|param| match param {
+                    GenericParam::Type(TypeParam { ident, .. })
+                    | GenericParam::Const(ConstParam { ident, .. }) => Some(format!("{{{ident}}}")),
+                    GenericParam::Lifetime(_) => None,
+                })
+                .collect();
+            let trait_desc = if trait_args.is_empty() {
+                trait_ident.to_string()
+            } else {
+                format!("{trait_ident}<{}>", trait_args.join(", "))
+            };
+            let trait_message =
+                format!("`{{Self}}` does not satisfy `where {predicates}` of `{trait_desc}`");
+            attrs.push(parse_quote!(#[diagnostic::on_unimplemented(message = #trait_message)]));
+            let type_eq_message = format!(
+                "`where` clause of `{trait_ident}` not satisfied: expected `{{T}}`, found `{{Self}}`"
+            );
+            let where_module_ident = Self::where_module_ident(trait_ident);
+            tokens.extend(quote! {
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub mod #where_module_ident {
+                    #[diagnostic::on_unimplemented(message = #type_eq_message)]
+                    pub trait TypeEq<T: ?Sized> {}
+
+                    impl<T: ?Sized> TypeEq<T> for T {}
+                }
+            });
+        }
         let trait_item = ItemTrait {
-            attrs: self.trait_def.attrs.clone(),
+            attrs,
             vis: self.trait_def.vis.clone(),
             unsafety: None,
             auto_token: None,
@@ -1503,6 +3206,9 @@ impl ToTokens for OutputItemTraitDef<'_> {
                         #full_variant_impl_body
                     }),
                 );
+                // Trait lifetimes are taken from the macro args, like the bounds that refer to them.
+                let variant_impl_generic_default_args =
+                    generalize(variant_impl_generic_default_args);
                 macro_variant_default_args
                     .extend(quote!(#variant_impl_generic_default_args #variant_ident #variant_generic_default_args => {}));
                 let mut impl_generics = TokenStream::new();
@@ -1533,9 +3239,41 @@ impl ToTokens for OutputItemTraitDef<'_> {
                     impl_args.append_separated(impl_generic_args, <Token![,]>::default());
                     variant.generics.gt_token.to_tokens(&mut impl_args);
                 }
+                let mut impl_where_clause = TokenStream::new();
+                if !type_eq_predicates.is_empty()
+                    && !self.has_recursive_bound(&output_variant.variant.impl_generics)
+                    && !self.has_recursive_bound(&variant.generics)
+                {
+                    let where_module_ident = Self::where_module_ident(trait_ident);
+                    let mut predicates = Vec::new();
+                    for predicate in &type_eq_predicates {
+                        let mut types = [predicate.left_ty.clone(), predicate.right_ty.clone()];
+                        for ty in &mut types {
+                            RemoveTypeBoundParamsFromPathArguments(&self.trait_def.generics)
+                                .visit_type_mut(ty);
+                            if let PathArguments::AngleBracketed(trait_args) =
+                                &output_variant.variant.trait_args
+                            {
+                                ty.substitute_all(&self.extracted_generics, trait_args)
+                                    .unwrap();
+                            }
+                            ty.substitute_all_params(
+                                &output_variant.variant.impl_generics,
+                                &renamed_variant_impl_generics,
+                            )
+                            .unwrap();
+                        }
+                        let [left_ty, right_ty] = types;
+                        predicates.push(generalize_variant(quote!(
+                            #left_ty: $(#ref_path_param::)*#where_module_ident::TypeEq<#right_ty>
+                        )));
+                    }
+                    impl_where_clause = quote!(where #(#predicates),*);
+                }
                 if independent_impls {
                     let impl_signature = quote!(
                         impl #impl_generics #name_param #trait_generic_args for $(#ref_path_param::)*#variant_ident #impl_args
+                            #impl_where_clause
                     );
                     macro_body.extend(quote! {
                         #impl_signature {
@@ -1602,9 +3340,26 @@ impl ToTokens for OutputItemTraitDef<'_> {
         // navigation fails. Therefore, we expand the macro ourselves, essentially duplicating
         // its contents in our output.
         tokens.extend(expand_macro_body(full_macro_body, &macro_default_args));
+
+        self.output_variants_macro(tokens);
+        self.output_defaults_trait(tokens);
+        self.output_extend_macro(tokens);
+
+        tokens.append_all(&self.warnings);
     }
 }
 
+// An item of an open enum trait that is implemented for each variant, together with its
+// implementation for variants that are added by extensions, if there is one.
+pub struct VariantDefault {
+    pub item_ident: Ident,
+    pub impl_item: Option<ImplItem>,
+    // For associated types with an implementation, the items of the trait output by
+    // `output_defaults_trait`.
+    pub defaults_trait_items: Option<(TraitItemType, ImplItem)>,
+    pub internal: bool,
+}
+
 #[derive(Clone)]
 pub struct ImplVariant {
     pub impl_generics: Generics,
@@ -1612,6 +3367,37 @@ pub struct ImplVariant {
     pub variant: TraitVariant,
 }
 
+// A warning, emitted as a use of a deprecated item because procedural macros cannot emit
+// warnings directly on stable Rust.
+pub struct OutputWarning {
+    span: Span,
+    message: String,
+}
+
+impl OutputWarning {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        OutputWarning {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+impl ToTokens for OutputWarning {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let message = &self.message;
+        let warning_ident = Ident::new("enum_trait_warning", self.span);
+        tokens.extend(quote!(
+            const _: () = {
+                #[deprecated = #message]
+                #[allow(non_camel_case_types)]
+                struct enum_trait_warning;
+                let _ = #warning_ident;
+            };
+        ));
+    }
+}
+
 pub struct OutputImplVariant {
     pub variant: ImplVariant,
     pub impl_items: ImplPartList<ImplItem>,
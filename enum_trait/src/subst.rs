@@ -39,7 +39,10 @@ impl VisitMut for ParamSubst<'_, '_> {
                 let span = i.span();
                 if let Some(arg) = self.substituted(self.arg.get_expr(span), span) {
                     let ty = &const_param.ty;
+                    // Const params are usually upper-case, so the binding needs to be exempted from
+                    // the naming lint.
                     *i = parse_quote!({
+                        #[allow(non_snake_case)]
                         let #ident: #ty = #arg;
                         #i
                     });
@@ -63,6 +66,23 @@ impl VisitMut for ParamSubst<'_, '_> {
                 return self.subst(i, self.arg.get_type(i.span()));
             }
         }
+        if let Type::Macro(TypeMacro { mac }) = i {
+            if mac.path.is_ident(INLINE_MATCH_MACRO_NAME) {
+                // Inline matches are only parsed during conversion, but can reference params.
+                match mac.parse_body::<TypeLevelExprMatch<TypeLevelExpr<Type>>>() {
+                    Ok(mut match_expr) => {
+                        match_expr.substitute_impl(self);
+                        mac.tokens = match_expr.into_token_stream();
+                    }
+                    Err(error) => {
+                        if self.result.is_ok() {
+                            self.result = Err(error);
+                        }
+                    }
+                }
+                return;
+            }
+        }
         visit_type_mut(self, i)
     }
 
@@ -481,8 +501,8 @@ impl<E: Substitutable> Substitutable for TypeLevelExpr<E> {
 
 impl<E: Substitutable> Substitutable for TypeLevelExprMatch<E> {
     fn substitute_impl(&mut self, subst: &mut ParamSubst) {
-        for ty in &mut self.types {
-            ty.substitute_impl(subst);
+        for match_type in &mut self.types {
+            match_type.substitute_impl(subst);
         }
         for arm in &mut self.arms {
             arm.substitute_impl(subst);
@@ -490,16 +510,25 @@ impl<E: Substitutable> Substitutable for TypeLevelExprMatch<E> {
     }
 }
 
+impl Substitutable for TypeLevelMatchType {
+    fn substitute_impl(&mut self, subst: &mut ParamSubst) {
+        self.ty.substitute_impl(subst);
+        self.bounds.substitute_impl(subst);
+    }
+}
+
 impl<E: Substitutable> Substitutable for TypeLevelArm<E> {
     fn substitute_impl(&mut self, subst: &mut ParamSubst) {
         subst.subst_with_multi_generics(
             self.selectors
                 .iter_mut()
-                .filter_map(|variant| match variant {
-                    TypeLevelArmSelector::Specific { generics, .. } => Some(generics),
-                    TypeLevelArmSelector::Default { .. } => None,
-                }),
-            |subst| self.body.substitute_impl(subst),
+                .flat_map(TypeLevelArmSelector::all_generics_mut),
+            |subst| {
+                if let Some((_, guard)) = &mut self.guard {
+                    guard.substitute_impl(subst);
+                }
+                self.body.substitute_impl(subst);
+            },
         )
     }
 }
@@ -676,6 +705,20 @@ pub fn add_underscores_to_all_params(generics: &mut Generics) -> Result<bool> {
     )
 }
 
+// Variant of `add_underscores_to_all_params` for the params of trait impls, where lifetime params
+// keep their names so that the bounds of type bound params can refer to them.
+pub fn add_underscores_to_non_lifetime_params(generics: &mut Generics) -> Result<bool> {
+    let generics_copy = generics.clone();
+    rename_conflicting_params(
+        generics,
+        |param| {
+            Ok(!matches!(param, GenericParam::Lifetime(_))
+                && param_generics_name_conflict(param, &generics_copy))
+        },
+        |_| {},
+    )
+}
+
 fn add_underscore_suffix(ident: &mut Ident) {
     *ident = ident_with_suffix(ident, "_", true)
 }
@@ -1147,6 +1190,7 @@ mod tests {
         assert_subst_expr(
             parse_quote!(f(A, B(A(C)))),
             parse_quote!({
+                #[allow(non_snake_case)]
                 let A: T = X;
                 f(A, B(A(C)))
             }),
@@ -1154,6 +1198,7 @@ mod tests {
         assert_subst_expr(
             parse_quote!(A(|A| 2 * A)),
             parse_quote!({
+                #[allow(non_snake_case)]
                 let A: T = X;
                 A(|A| 2 * A)
             }),
@@ -1178,6 +1223,7 @@ mod tests {
                 C => X,
                 D => B,
                 E => {
+                    #[allow(non_snake_case)]
                     let A: T = X;
                     f(A)
                 },
@@ -1227,6 +1273,7 @@ mod tests {
         assert_subst_expr(
             parse_quote!(f(A, B(A(C)))),
             parse_quote!({
+                #[allow(non_snake_case)]
                 let A: T = X + 42;
                 f(A, B(A(C)))
             }),
@@ -1234,6 +1281,7 @@ mod tests {
         assert_subst_expr(
             parse_quote!(A(|A| 2 * A)),
             parse_quote!({
+                #[allow(non_snake_case)]
                 let A: T = X + 42;
                 A(|A| 2 * A)
             }),
@@ -1258,6 +1306,7 @@ mod tests {
                 C => X + 42,
                 D => B,
                 E => {
+                    #[allow(non_snake_case)]
                     let A: T = X + 42;
                     f(A)
                 },
@@ -1,5 +1,9 @@
 #![no_std]
 
+// Allows the default operator and number paths of `meta!`, which refer to this crate by name, to
+// be used within this crate.
+extern crate self as enum_trait_core;
+
 pub mod meta_bool;
 pub mod meta_num;
 pub mod optional_type;
@@ -22,9 +22,9 @@ meta! {
         ///
         /// All matching on `MetaBool` instances could be regarded as special cases of `IfElse`,
         /// except that `IfElse` would need to be generic over a type bound, which is not possible.
-        /// In fact, this limitation makes `IfElse` almost useless. Instead, to match on a
-        /// `MetaBool`, define a trait alias and then use `match`, as described in the documentation
-        /// of the `enum_trait` crate.
+        /// In fact, this limitation makes `IfElse` almost useless. Instead, define a trait alias
+        /// and then use `if <B> { ... } else { ... }` or `match`, as described in the
+        /// documentation of the `enum_trait` crate.
         pub type IfElse<'a, TrueT: ?Sized + 'a, FalseT: ?Sized + 'a>: ?Sized + 'a = match <Self> {
             False => FalseT,
             True => TrueT,
@@ -25,7 +25,7 @@ meta! {
 
         pub type IsZero: MetaBool = match <Self> {
             Zero => True,
-            Succ<N: MetaNum> => False,
+            Succ<_> => False,
         };
 
         pub type IsEven: MetaBool = match <Self> {
@@ -42,15 +42,17 @@ meta! {
         #[cfg(all(feature = "typenum", not(feature = "generic-array")))]
         pub type ToTypeNum: typenum::Unsigned = match <Self> {
             Zero => typenum::UTerm,
-            Succ<N: MetaNum> => typenum::UInt<<Div2Ceil<N> as MetaNum>::ToTypeNum,
-                                              <N::IsEven as MetaBool>::ToTypeNum>,
+            Succ<N: MetaNum> => let Half = Div2Ceil<N>;
+                                let Even: MetaBool = N::IsEven;
+                                in typenum::UInt<Half::ToTypeNum, Even::ToTypeNum>,
         };
 
         /// Converts this `MetaNum` instance to the corresponding type of the `typenum` crate.
         #[cfg(feature = "generic-array")]
         pub type ToTypeNum: typenum::Unsigned + generic_array::ArrayLength = match <Self> {
             Zero => typenum::UTerm,
-            Succ<N: MetaNum> => ConstructUInt<<Div2Ceil<N> as MetaNum>::ToTypeNum, N::IsEven>,
+            Succ<N: MetaNum> => let Half = Div2Ceil<N>;
+                                in ConstructUInt<Half::ToTypeNum, N::IsEven>,
         };
     }
 
@@ -61,12 +63,12 @@ meta! {
 
     pub type Mul<M: MetaNum, N: MetaNum>: MetaNum = match <N> {
         Zero => Zero,
-        Succ<P: MetaNum> => Add<M, Mul<M, P>>,
+        Succ<P: MetaNum> => M + Mul<M, P>,
     };
 
     pub type Pow<M: MetaNum, N: MetaNum>: MetaNum = match <N> {
-        Zero => Succ<Zero>,
-        Succ<P: MetaNum> => Mul<M, Pow<M, P>>,
+        Zero => 1,
+        Succ<P: MetaNum> => M * Pow<M, P>,
     };
 
     pub type Equal<M: MetaNum, N: MetaNum>: MetaBool = match <M> {
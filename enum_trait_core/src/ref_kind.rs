@@ -2,7 +2,7 @@ use core::{ops::Deref, slice};
 
 use enum_trait::meta;
 
-use crate::meta_bool::*;
+use crate::{meta_bool::*, type_list::*};
 
 meta! {
     pub enum trait RefKind {
@@ -20,6 +20,12 @@ meta! {
             Shared => &'a T,
             Mutable => &'a mut T,
         };
+
+        pub type MapToRefs<'a, 'b, List: SizedTypeList<'a>>: SizedTypeList<'b> where 'a: 'b =
+            match <Self> {
+                Shared => <List as TypeList<'a>>::MapToRefs<'b>,
+                Mutable => <List as TypeList<'a>>::MapToMutRefs<'b>,
+            };
     }
 
     // Note that this can also be implemented using the `IntoIterator` instance of slice references,
@@ -36,14 +42,55 @@ meta! {
         }
     }
 
-    // TODO: Use `nested_tuple_ref_to_ref_tuple` and `nested_tuple_ref_to_ref_tuple_mut` instead of
-    //       pairs, once available.
+    pub fn nested_tuple_ref_to_ref_tuple_of_kind<'a, 'b, K: RefKind, List: SizedTypeList<'a>, T>(
+        tuple: K::Ref<'b, NestedTupleWith<'a, List, T>>,
+    ) -> NestedTupleWith<'b, K::MapToRefs<'a, 'b, List>, K::Ref<'b, T>>
+    where
+        'a: 'b,
+    {
+        match <K> {
+            Shared => nested_tuple_ref_to_ref_tuple::<List, T>(tuple),
+            Mutable => nested_tuple_ref_to_ref_tuple_mut::<List, T>(tuple),
+        }
+    }
+
+    // A pair is a nested tuple with a single item, followed by the second element of the pair.
     pub fn pair_ref_to_ref_pair<'a, K: RefKind, T1, T2>(
         pair: K::Ref<'a, (T1, T2)>,
     ) -> (K::Ref<'a, T1>, K::Ref<'a, T2>) {
         match <K> {
-            Shared => (&pair.0, &pair.1),
-            Mutable => (&mut pair.0, &mut pair.1),
+            Shared => nested_tuple_ref_to_ref_tuple::<type_list![T1], T2>(pair),
+            Mutable => nested_tuple_ref_to_ref_tuple_mut::<type_list![T1], T2>(pair),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ref_pairs() {
+        let mut pair = (42, "test");
+        let (x, s) = pair_ref_to_ref_pair::<Shared, _, _>(&pair);
+        assert_eq!((*x, *s), (42, "test"));
+        let (x, _) = pair_ref_to_ref_pair::<Mutable, _, _>(&mut pair);
+        *x += 1;
+        assert_eq!(pair, (43, "test"));
+    }
+
+    #[test]
+    fn nested_ref_tuples() {
+        let mut tuple = (42, ("test", true));
+        let (x, (s, b)) =
+            nested_tuple_ref_to_ref_tuple_of_kind::<Shared, type_list![i32, &str], bool>(&tuple);
+        assert_eq!((*x, *s, *b), (42, "test", true));
+        let (x, (_, b)) =
+            nested_tuple_ref_to_ref_tuple_of_kind::<Mutable, type_list![i32, &str], bool>(
+                &mut tuple,
+            );
+        *x += 1;
+        *b = false;
+        assert_eq!(tuple, (43, ("test", false)));
+    }
+}
@@ -3,77 +3,74 @@ use enum_trait::meta;
 use crate::{meta_bool::*, meta_num::*, optional_type::*};
 
 meta! {
-    pub enum trait TypeList<trait ItemBound: ?Sized> {
+    pub enum trait TypeList<'a, trait ItemBound: ?Sized + 'a> {
         Empty,
-        NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>>,
+        NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>>,
     }
 
-    trait impl<trait ItemBound: ?Sized> TypeList<ItemBound> {
+    trait impl<'a, trait ItemBound: ?Sized + 'a> TypeList<'a, ItemBound> {
         pub type IsEmpty: MetaBool = match <Self> {
             Empty => True,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>> => False,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>> => False,
         };
 
         pub type Len: MetaNum = match <Self> {
             Empty => Zero,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>> => Succ<Tail::Len>,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>> => Succ<Tail::Len>,
         };
 
-        pub type Get<I: ValidIndex<ItemBound, Self>>: ItemBound = match <Self, I> {
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>>, Zero => Head,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>>, Succ<P: ValidIndex<ItemBound, Tail>> =>
-                <Tail as TypeList<ItemBound>>::Get<P>,
+        pub type Get<I: ValidIndex<'a, ItemBound, Self>>: ItemBound = match <Self, I> {
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>>, Zero => Head,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>>, Succ<P: ValidIndex<'a, ItemBound, Tail>> =>
+                <Tail as TypeList<'a, ItemBound>>::Get<P>,
         };
 
-        pub type GetOpt<I: ExtendedIndex<ItemBound, Self>>: OptionalType<ItemBound> = match <Self, I> {
+        pub type GetOpt<I: ExtendedIndex<'a, ItemBound, Self>>: OptionalType<ItemBound> = match <Self, I> {
             Empty, Zero => NoType,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>>, Zero => SomeType<Head>,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>>, Succ<P: ExtendedIndex<ItemBound, Tail>> =>
-                <Tail as TypeList<ItemBound>>::GetOpt<P>,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>>, Zero => SomeType<Head>,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>>, Succ<P: ExtendedIndex<'a, ItemBound, Tail>> =>
+                <Tail as TypeList<'a, ItemBound>>::GetOpt<P>,
         };
 
-        pub type Append<T: ItemBound>: TypeList<ItemBound> = match <Self> {
+        pub type Append<T: ItemBound>: TypeList<'a, ItemBound> = match <Self> {
             Empty => NonEmpty<T, Empty>,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>> =>
-                NonEmpty<Head, <Tail as TypeList<ItemBound>>::Append<T>>,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>> =>
+                NonEmpty<Head, <Tail as TypeList<'a, ItemBound>>::Append<T>>,
         };
 
-        pub type AppendAll<List: TypeList<ItemBound>>: TypeList<ItemBound> = match <Self> {
+        pub type AppendAll<List: TypeList<'a, ItemBound>>: TypeList<'a, ItemBound> = match <Self> {
             Empty => List,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>> =>
-                NonEmpty<Head, <Tail as TypeList<ItemBound>>::AppendAll<List>>,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>> =>
+                NonEmpty<Head, <Tail as TypeList<'a, ItemBound>>::AppendAll<List>>,
         };
 
-        pub type Reverse: TypeList<ItemBound> = match <Self> {
+        pub type Reverse: TypeList<'a, ItemBound> = match <Self> {
             Empty => Empty,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>> =>
-                <<Tail as TypeList<ItemBound>>::Reverse as TypeList<ItemBound>>::Append<Head>,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>> =>
+                <<Tail as TypeList<'a, ItemBound>>::Reverse as TypeList<'a, ItemBound>>::Append<Head>,
         };
 
-        // TODO: These require a lifetime parameter `'a` at `TypeList`, with `ItemBound: 'a` and
-        //       'a: 'b`.
-        /*
-        pub type MapToRefs<'b>: SizedTypeList = match <Self> {
+        pub type MapToRefs<'b>: SizedTypeList<'b> where 'a: 'b = match <Self> {
             Empty => Empty,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>> =>
-                NonEmpty<&'b Head, <Tail as TypeList<ItemBound>>::MapToRefs<'b>>,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>> =>
+                NonEmpty<&'b Head, <Tail as TypeList<'a, ItemBound>>::MapToRefs<'b>>,
         };
 
-        pub type MapToMutRefs<'b>: SizedTypeList = match <Self> {
+        pub type MapToMutRefs<'b>: SizedTypeList<'b> where 'a: 'b = match <Self> {
             Empty => Empty,
-            NonEmpty<Head: ItemBound, Tail: TypeList<ItemBound>> =>
-                NonEmpty<&'b mut Head, <Tail as TypeList<ItemBound>>::MapToMutRefs<'b>>,
+            NonEmpty<Head: ItemBound, Tail: TypeList<'a, ItemBound>> =>
+                NonEmpty<&'b mut Head, <Tail as TypeList<'a, ItemBound>>::MapToMutRefs<'b>>,
         };
-        */
     }
 
-    pub trait ValidIndex<trait ItemBound: ?Sized, List: TypeList<ItemBound>> =
+    pub trait ValidIndex<'a, trait ItemBound: ?Sized + 'a, List: TypeList<'a, ItemBound>> =
         MetaNumLessThan<List::Len>;
 
-    pub trait ExtendedIndex<trait ItemBound: ?Sized, List: TypeList<ItemBound>> =
+    pub trait ExtendedIndex<'a, trait ItemBound: ?Sized + 'a, List: TypeList<'a, ItemBound>> =
         MetaNumLessOrEqual<List::Len>;
 
-    pub trait SizedTypeList<trait ItemBound: Sized> = TypeList<
+    pub trait SizedTypeList<'a, trait ItemBound: Sized + 'a> = TypeList<
+        'a,
         ItemBound,
         trait ValidIndex = SizedValidIndex,
         trait ExtendedIndex = SizedExtendedIndex,
@@ -81,96 +78,112 @@ meta! {
     >;
 
     pub trait SizedValidIndex<
-        trait ItemBound: Sized,
-        List: SizedTypeList<ItemBound>,
+        'a,
+        trait ItemBound: Sized + 'a,
+        List: SizedTypeList<'a, ItemBound>,
     > = ValidIndex<
+        'a,
         ItemBound,
         List,
         trait TypeList = SizedTypeList,
     >;
 
     pub trait SizedExtendedIndex<
-        trait ItemBound: Sized,
-        List: SizedTypeList<ItemBound>,
+        'a,
+        trait ItemBound: Sized + 'a,
+        List: SizedTypeList<'a, ItemBound>,
     > = ExtendedIndex<
+        'a,
         ItemBound,
         List,
         trait TypeList = SizedTypeList,
         trait OptionalType = SizedOptionalType,
     >;
 
-    pub type NestedTupleWith<trait ItemBound: Sized, List: SizedTypeList<ItemBound>, T: Sized>: Sized =
+    pub type NestedTupleWith<'a, trait ItemBound: Sized + 'a, List: SizedTypeList<'a, ItemBound>, T: Sized>: Sized =
         match <List> {
             Empty => T,
-            NonEmpty<Head: ItemBound, Tail: SizedTypeList<ItemBound>> =>
-                (Head, NestedTupleWith<ItemBound, Tail, T>),
+            NonEmpty<Head: ItemBound, Tail: SizedTypeList<'a, ItemBound>> =>
+                (Head, NestedTupleWith<'a, ItemBound, Tail, T>),
         };
 
-    pub type NestedTuple<trait ItemBound: Sized, List: SizedTypeList<ItemBound>>: Sized =
-        NestedTupleWith<ItemBound, List, ()>;
+    pub type NestedTuple<'a, trait ItemBound: Sized + 'a, List: SizedTypeList<'a, ItemBound>>: Sized =
+        NestedTupleWith<'a, ItemBound, List, ()>;
 
     pub fn nested_tuple_item<
-        trait ItemBound: Sized,
-        List: SizedTypeList<ItemBound>,
+        'a,
+        'b,
+        trait ItemBound: Sized + 'a,
+        List: SizedTypeList<'a, ItemBound>,
         T: Sized,
-        I: SizedExtendedIndex<ItemBound, List>,
+        I: SizedExtendedIndex<'a, ItemBound, List>,
     >(
-        tuple: &NestedTupleWith<ItemBound, List, T>,
-    ) -> &<<List as SizedTypeList<ItemBound>>::GetOpt<I> as SizedOptionalType>::UnwrapOr<T> {
+        tuple: &'b NestedTupleWith<'a, ItemBound, List, T>,
+    ) -> &'b <<List as SizedTypeList<'a, ItemBound>>::GetOpt<I> as SizedOptionalType>::UnwrapOr<T> {
         match <List, I> {
             Empty, Zero => tuple,
-            NonEmpty<Head: ItemBound, Tail: SizedTypeList<ItemBound>>, Zero => &tuple.0,
-            NonEmpty<Head: ItemBound, Tail: SizedTypeList<ItemBound>>, Succ<P: SizedExtendedIndex<ItemBound, Tail>> =>
+            NonEmpty<Head: ItemBound, Tail: SizedTypeList<'a, ItemBound>>, Zero => &tuple.0,
+            NonEmpty<Head: ItemBound, Tail: SizedTypeList<'a, ItemBound>>, Succ<P: SizedExtendedIndex<'a, ItemBound, Tail>> =>
                 nested_tuple_item::<ItemBound, Tail, T, P>(&tuple.1),
         }
     }
 
     pub fn nested_tuple_item_mut<
-        trait ItemBound: Sized,
-        List: SizedTypeList<ItemBound>,
+        'a,
+        'b,
+        trait ItemBound: Sized + 'a,
+        List: SizedTypeList<'a, ItemBound>,
         T: Sized,
-        I: SizedExtendedIndex<ItemBound, List>,
+        I: SizedExtendedIndex<'a, ItemBound, List>,
     >(
-        tuple: &mut NestedTupleWith<ItemBound, List, T>,
-    ) -> &mut <<List as SizedTypeList<ItemBound>>::GetOpt<I> as SizedOptionalType>::UnwrapOr<T> {
+        tuple: &'b mut NestedTupleWith<'a, ItemBound, List, T>,
+    ) -> &'b mut <<List as SizedTypeList<'a, ItemBound>>::GetOpt<I> as SizedOptionalType>::UnwrapOr<T> {
         match <List, I> {
             Empty, Zero => tuple,
-            NonEmpty<Head: ItemBound, Tail: SizedTypeList<ItemBound>>, Zero => &mut tuple.0,
-            NonEmpty<Head: ItemBound, Tail: SizedTypeList<ItemBound>>, Succ<P: SizedExtendedIndex<ItemBound, Tail>> =>
+            NonEmpty<Head: ItemBound, Tail: SizedTypeList<'a, ItemBound>>, Zero => &mut tuple.0,
+            NonEmpty<Head: ItemBound, Tail: SizedTypeList<'a, ItemBound>>, Succ<P: SizedExtendedIndex<'a, ItemBound, Tail>> =>
                 nested_tuple_item_mut::<ItemBound, Tail, T, P>(&mut tuple.1),
         }
     }
 
-    // TODO: See comment at `MapToRefs`.
-    /*
+    // Type bound args are erased, so passing `ItemBound` for the mapped list is not a problem.
     pub fn nested_tuple_ref_to_ref_tuple<
-        trait ItemBound: Sized,
-        List: SizedTypeList<ItemBound>,
+        'a,
+        'b,
+        trait ItemBound: Sized + 'a,
+        List: SizedTypeList<'a, ItemBound>,
         T: Sized,
     >(
-        tuple: &NestedTupleWith<ItemBound, List, T>,
-    ) -> NestedTupleWith<<List as TypeList<ItemBound>>::MapToRefs<'_>, &T> {
+        tuple: &'b NestedTupleWith<'a, ItemBound, List, T>,
+    ) -> NestedTupleWith<'b, ItemBound, <List as TypeList<'a, ItemBound>>::MapToRefs<'b>, &'b T>
+    where
+        'a: 'b,
+    {
         match <List> {
             Empty => tuple,
-            NonEmpty<Head: ItemBound, Tail: SizedTypeList<ItemBound>> =>
+            NonEmpty<Head: ItemBound, Tail: SizedTypeList<'a, ItemBound>> =>
                 (&tuple.0, nested_tuple_ref_to_ref_tuple::<ItemBound, Tail, T>(&tuple.1)),
         }
     }
 
     pub fn nested_tuple_ref_to_ref_tuple_mut<
-        trait ItemBound: Sized,
-        List: SizedTypeList<ItemBound>,
+        'a,
+        'b,
+        trait ItemBound: Sized + 'a,
+        List: SizedTypeList<'a, ItemBound>,
         T: Sized,
     >(
-        tuple: &mut NestedTupleWith<ItemBound, List, T>,
-    ) -> NestedTupleWith<<List as TypeList<ItemBound>>::MapToMutRefs<'_>, &mut T> {
+        tuple: &'b mut NestedTupleWith<'a, ItemBound, List, T>,
+    ) -> NestedTupleWith<'b, ItemBound, <List as TypeList<'a, ItemBound>>::MapToMutRefs<'b>, &'b mut T>
+    where
+        'a: 'b,
+    {
         match <List> {
             Empty => tuple,
-            NonEmpty<Head: ItemBound, Tail: SizedTypeList<ItemBound>> =>
+            NonEmpty<Head: ItemBound, Tail: SizedTypeList<'a, ItemBound>> =>
                 (&mut tuple.0, nested_tuple_ref_to_ref_tuple_mut::<ItemBound, Tail, T>(&mut tuple.1)),
         }
     }
-    */
 }
 
 #[macro_export]
@@ -184,7 +197,7 @@ macro_rules! type_list {
         enum_trait::iterate!(
             $n,
             $crate::type_list::Empty,
-            |<List: TypeList>| $crate::type_list::NonEmpty<$Item, List>,
+            |<List: TypeList<'_>>| $crate::type_list::NonEmpty<$Item, List>,
         )
     );
 }
@@ -200,7 +213,7 @@ mod tests {
     type EmptyTypeList = type_list![];
     type TwoItemTypeList = type_list![&'static str, u8];
     type ThreeItemTypeList = type_list![bool; 3];
-    type FiveItemTypeList = <TwoItemTypeList as TypeList>::AppendAll<ThreeItemTypeList>;
+    type FiveItemTypeList = <TwoItemTypeList as TypeList<'static>>::AppendAll<ThreeItemTypeList>;
 
     #[const_test]
     const fn properties() {
@@ -262,4 +275,19 @@ mod tests {
             &true
         );
     }
+
+    #[test]
+    fn nested_ref_tuples() {
+        let mut tuple = (42, ("test", true));
+        let (x, (s, (b, _))) = nested_tuple_ref_to_ref_tuple::<type_list![i32, &str, bool], ()>(&(
+            42,
+            ("test", (true, ())),
+        ));
+        assert_eq!((*x, *s, *b), (42, "test", true));
+        let (x, (_, b)) =
+            nested_tuple_ref_to_ref_tuple_mut::<type_list![i32, &str], bool>(&mut tuple);
+        *x += 1;
+        *b = false;
+        assert_eq!(tuple, (43, ("test", false)));
+    }
 }
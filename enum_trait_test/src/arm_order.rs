@@ -0,0 +1,99 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, meta_num::*};
+
+meta! {
+    pub trait OrderMetaBool = MetaBool;
+
+    pub type Negate<B: OrderMetaBool>: MetaBool = match <B> {
+        True => False,
+        False => True,
+    };
+
+    pub trait OrderMetaNum = MetaNum;
+
+    trait impl OrderMetaNum {
+        pub type Double: MetaNum = match <Self> {
+            Succ<P: OrderMetaNum> => Succ<Succ<P::Double>>,
+            Zero => Zero,
+        };
+
+        pub const IS_ZERO: bool = match <Self> {
+            Zero => true,
+            Succ<P: MetaNum> => false,
+        };
+    }
+
+    pub enum trait Direction {
+        North,
+        East,
+        South,
+        West,
+    }
+
+    pub trait Heading = Direction;
+
+    trait impl Heading {
+        pub type Opposite: Direction = match <Self> {
+            West => East,
+            South => North,
+            East => West,
+            North => South,
+        };
+    }
+
+    trait impl Direction {
+        pub type IsVertical: MetaBool = match <Self> {
+            North | South => True,
+            East | West => False,
+        };
+    }
+
+    pub trait Vertical = Direction where Self::IsVertical = True;
+
+    pub type Flip<D: Vertical>: Vertical = match <D> {
+        North => South,
+        South => North,
+    };
+
+    pub trait UpDown = Vertical;
+
+    pub type IsUp<D: UpDown>: MetaBool = match <D> {
+        South => False,
+        North => True,
+    };
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(Negate<False>, True);
+assert_type_eq!(Negate<True>, False);
+
+assert_type_eq!(<Zero as OrderMetaNum>::Double, Zero);
+assert_type_eq!(
+    <Succ<Succ<Zero>> as OrderMetaNum>::Double,
+    Succ<Succ<Succ<Succ<Zero>>>>
+);
+
+assert_type_eq!(<North as Heading>::Opposite, South);
+assert_type_eq!(<West as Heading>::Opposite, East);
+
+assert_type_eq!(IsUp<North>, True);
+assert_type_eq!(IsUp<South>, False);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_zero() {
+        assert!(Zero::IS_ZERO);
+        assert!(!Succ::<Zero>::IS_ZERO);
+    }
+}
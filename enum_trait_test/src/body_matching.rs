@@ -0,0 +1,160 @@
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, ref_kind::*};
+
+meta! {
+    pub trait BodyMetaBool = MetaBool;
+
+    pub trait BodyRefKind = RefKind;
+
+    pub fn sum<K: BodyRefKind>(slice: K::Ref<'_, [u32]>) -> u32 {
+        let mut sum = 0;
+        let iter = match <K> {
+            Shared => slice.iter(),
+            Mutable => slice.iter_mut(),
+        };
+        for item in iter {
+            sum += *item;
+        }
+        sum
+    }
+
+    pub fn sum_and_reset<B: BodyMetaBool>(values: &mut [u32]) -> u32 {
+        let mut sum = 0;
+        for value in values.iter_mut() {
+            sum += *value;
+            match <B> {
+                False => {}
+                True => *value = 0,
+            }
+        }
+        sum
+    }
+
+    pub fn scaled<B: BodyMetaBool>(n: u32) -> u32 {
+        let factor = 2;
+        factor * match <B> {
+            False => n + 1,
+            True => n,
+        }
+    }
+
+    pub fn count_even<B: BodyMetaBool>(n: u32) -> u32 {
+        let mut count = 0;
+        let mut i = 0;
+        loop {
+            if i >= n {
+                break;
+            }
+            if i % 2 == 0 {
+                count += match <B> {
+                    False => 1,
+                    True => i,
+                };
+            }
+            i += 1;
+        }
+        count
+    }
+
+    pub fn narrow<A: BodyMetaBool, B: BodyMetaBool>(n: u32) -> u32 {
+        let narrowed: match <A> {
+            False => u8,
+            True => u16,
+        } = n as _;
+        let offset = match <B> {
+            False => 0,
+            True => match <A> {
+                False => 1,
+                True => 2,
+            },
+        };
+        narrowed as u32 + offset
+    }
+
+    pub fn offset_len<B: BodyMetaBool>(n: u32) -> usize {
+        let mut offset = 1;
+        offset += n;
+        let mut values: Vec<u32> = Vec::new();
+        match <B> {
+            False => 0,
+            True => {
+                values.push(offset);
+                values.len() + offset as usize
+            }
+        }
+    }
+
+    pub fn as_number<B: BodyMetaBool>(n: u32) -> u32 {
+        // Not a type-level match.
+        match <B as MetaBool>::VALUE {
+            true => n,
+            false => 0,
+        }
+    }
+
+    trait impl BodyMetaBool {
+        pub fn describe(n: u32) -> String {
+            let prefix = format!("{n}: ");
+            let description = match <Self> {
+                False => "no",
+                True => "yes",
+            };
+            prefix + description
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surrounding_statements() {
+        let mut values = [1, 2, 3];
+        assert_eq!(sum::<Shared>(&values), 6);
+        assert_eq!(sum::<Mutable>(&mut values), 6);
+        assert_eq!(sum_and_reset::<False>(&mut values), 6);
+        assert_eq!(values, [1, 2, 3]);
+        assert_eq!(sum_and_reset::<True>(&mut values), 6);
+        assert_eq!(values, [0, 0, 0]);
+    }
+
+    #[test]
+    fn operands() {
+        assert_eq!(scaled::<False>(3), 8);
+        assert_eq!(scaled::<True>(3), 6);
+    }
+
+    #[test]
+    fn nested_in_control_flow() {
+        assert_eq!(count_even::<False>(5), 3);
+        assert_eq!(count_even::<True>(5), 6);
+    }
+
+    #[test]
+    fn multiple_matches() {
+        assert_eq!(narrow::<False, False>(300), 44);
+        assert_eq!(narrow::<True, False>(300), 300);
+        assert_eq!(narrow::<False, True>(300), 45);
+        assert_eq!(narrow::<True, True>(300), 302);
+    }
+
+    #[test]
+    fn mutated_locals() {
+        assert_eq!(offset_len::<False>(2), 0);
+        assert_eq!(offset_len::<True>(2), 4);
+    }
+
+    #[test]
+    fn runtime_matches() {
+        assert_eq!(as_number::<False>(3), 0);
+        assert_eq!(as_number::<True>(3), 3);
+    }
+
+    #[test]
+    fn trait_impl_matches() {
+        assert_eq!(False::describe(1), "1: no");
+        assert_eq!(True::describe(2), "2: yes");
+    }
+}
@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+meta! {
+    pub enum trait TestList {
+        Nil,
+        Cons<Head, Tail: TestList>,
+    }
+
+    trait impl TestList {
+        pub type Zip<Other: TestList>: TestList = match <Self, Other> {
+            _, Nil => Nil,
+            Nil, Cons<Head, Tail: TestList> => Nil,
+            Cons<Head1, Tail1: TestList>, Cons<Head2, Tail2: TestList> => Cons<(Head1, Head2), Tail1::Zip<Tail2>>,
+        };
+
+        pub type ZipRev<Other: TestList>: TestList = match <Other, Self> {
+            Nil, Nil => Nil,
+            Cons<Head, Tail: TestList>, Nil => Nil,
+            Nil, Cons<Head, Tail: TestList> => Nil,
+            Cons<Head1, Tail1: TestList>, Cons<Head2, Tail2: TestList> => Cons<(Head1, Head2), Tail2::ZipRev<Tail1>>,
+        };
+    }
+
+    pub trait CombinedMetaBool = MetaBool;
+
+    pub type XOr<A: CombinedMetaBool, B: CombinedMetaBool>: MetaBool = match <A, B> {
+        False, False => False,
+        True, False => True,
+        False, True => True,
+        True, True => False,
+    };
+
+    pub type And<A: CombinedMetaBool, B: CombinedMetaBool>: MetaBool = match <A, B> {
+        True, True => True,
+        _, _ => False,
+    };
+
+    pub fn both<A: CombinedMetaBool, B: CombinedMetaBool>() -> bool {
+        match <A, B> {
+            _, False => false,
+            False, True => false,
+            True, True => true,
+        }
+    }
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(<Nil as TestList>::Zip<Cons<u8, Nil>>, Nil);
+assert_type_eq!(<Cons<u8, Nil> as TestList>::Zip<Nil>, Nil);
+assert_type_eq!(
+    <Cons<u8, Cons<u16, Nil>> as TestList>::Zip<Cons<i8, Nil>>,
+    Cons<(u8, i8), Nil>,
+);
+assert_type_eq!(
+    <Cons<u8, Cons<u16, Nil>> as TestList>::Zip<Cons<i8, Cons<i16, Cons<i32, Nil>>>>,
+    Cons<(u8, i8), Cons<(u16, i16), Nil>>,
+);
+
+assert_type_eq!(
+    <Cons<u8, Cons<u16, Nil>> as TestList>::ZipRev<Cons<i8, Cons<i16, Nil>>>,
+    Cons<(i8, u8), Cons<(i16, u16), Nil>>,
+);
+
+assert_type_eq!(XOr<False, False>, False);
+assert_type_eq!(XOr<False, True>, True);
+assert_type_eq!(XOr<True, False>, True);
+assert_type_eq!(XOr<True, True>, False);
+
+assert_type_eq!(And<False, False>, False);
+assert_type_eq!(And<False, True>, False);
+assert_type_eq!(And<True, False>, False);
+assert_type_eq!(And<True, True>, True);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_true() {
+        assert!(!both::<False, False>());
+        assert!(!both::<False, True>());
+        assert!(!both::<True, False>());
+        assert!(both::<True, True>());
+    }
+}
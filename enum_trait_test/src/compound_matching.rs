@@ -0,0 +1,103 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, meta_num::*};
+
+meta! {
+    pub enum trait Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    trait impl Color {
+        pub type Next: Color = match <Self> {
+            Red => Green,
+            Green => Blue,
+            Blue => Red,
+        };
+
+        pub type AfterNext: Color = match <Self::Next> {
+            Red => Green,
+            Green => Blue,
+            Blue => Red,
+        };
+
+        pub type NextUnlessRed: Color = match <Self::Next, Self> {
+            Red, Red => Red,
+            Green, Red => Red,
+            Blue, Red => Red,
+            Red, Green => Red,
+            Green, Green => Green,
+            Blue, Green => Blue,
+            Red, Blue => Red,
+            Green, Blue => Green,
+            Blue, Blue => Blue,
+        };
+    }
+
+    pub type NextOf<C: Color>: Color = match <<C as Color>::Next> {
+        Red => Red,
+        Green => Green,
+        Blue => Blue,
+    };
+
+    pub fn next_name<C: Color>() -> &'static str {
+        match <C::Next> {
+            Red => "red",
+            Green => "green",
+            Blue => "blue",
+        }
+    }
+
+    pub trait CompoundMetaNum = MetaNum;
+
+    pub type IsSumZero<M: MetaNum, N: MetaNum>: MetaBool = match <Add<M, N>: CompoundMetaNum> {
+        Zero => True,
+        Succ<P: MetaNum> => False,
+    };
+
+    pub trait CompoundMetaBool = MetaBool;
+
+    pub type IsNonZero<N: MetaNum>: MetaBool = match <N::IsZero: CompoundMetaBool> {
+        False => True,
+        True => False,
+    };
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(<Red as Color>::AfterNext, Blue);
+assert_type_eq!(<Green as Color>::AfterNext, Red);
+assert_type_eq!(<Blue as Color>::AfterNext, Green);
+
+assert_type_eq!(<Red as Color>::NextUnlessRed, Red);
+assert_type_eq!(<Green as Color>::NextUnlessRed, Blue);
+assert_type_eq!(<Blue as Color>::NextUnlessRed, Red);
+
+assert_type_eq!(NextOf<Red>, Green);
+assert_type_eq!(NextOf<Blue>, Red);
+
+assert_type_eq!(IsSumZero<meta_num!(0), meta_num!(0)>, True);
+assert_type_eq!(IsSumZero<meta_num!(0), meta_num!(1)>, False);
+assert_type_eq!(IsSumZero<meta_num!(2), meta_num!(0)>, False);
+
+assert_type_eq!(IsNonZero<meta_num!(0)>, False);
+assert_type_eq!(IsNonZero<meta_num!(3)>, True);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_names() {
+        assert_eq!(next_name::<Red>(), "green");
+        assert_eq!(next_name::<Green>(), "blue");
+        assert_eq!(next_name::<Blue>(), "red");
+    }
+}
@@ -0,0 +1,70 @@
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+meta! {
+    pub enum trait Token {
+        Lit<const N: usize>,
+        Tag<const C: char>,
+        Pair<A: Token, B: Token>,
+    }
+
+    trait impl Token {
+        pub const WEIGHT: usize = match <Self> {
+            Lit<const N: usize> => N,
+            Tag<const C: char> => C as usize,
+            Pair<A: Token, B: Token> => A::WEIGHT + B::WEIGHT,
+        };
+
+        pub type IsLit: MetaBool = match <Self> {
+            Lit<_> => True,
+            _ => False,
+        };
+
+        pub fn describe() -> String {
+            match <Self> {
+                Lit<const N: usize> => N.to_string(),
+                Tag<const C: char> => C.to_string(),
+                Pair<A: Token, B: Token> => [A::describe(), B::describe()].join(", "),
+            }
+        }
+    }
+
+    pub enum trait Capped<const MAX: usize> {
+        Value<const N: usize>,
+        Unbounded,
+    }
+
+    trait impl<const MAX: usize> Capped<{ MAX }> {
+        pub const CAPPED: usize = match <Self> {
+            Value<const N: usize> => if N < MAX { N } else { MAX },
+            Unbounded => MAX,
+        };
+    }
+
+    pub fn tag_or<T: Token>(default: char) -> char {
+        match <T> {
+            Tag<const C: char> => C,
+            _ => default,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn const_params() {
+        assert_eq!(<Lit<42> as Token>::WEIGHT, 42);
+        assert_eq!(<Pair<Lit<1>, Tag<'a'>> as Token>::WEIGHT, 98);
+        assert!(<Lit<0> as Token>::IsLit::VALUE);
+        assert!(!<Tag<'x'> as Token>::IsLit::VALUE);
+        assert_eq!(<Pair<Lit<1>, Tag<'x'>> as Token>::describe(), "1, x");
+        assert_eq!(<Value<3> as Capped<5>>::CAPPED, 3);
+        assert_eq!(<Value<7> as Capped<5>>::CAPPED, 5);
+        assert_eq!(<Unbounded as Capped<5>>::CAPPED, 5);
+        assert_eq!(tag_or::<Tag<'x'>>('-'), 'x');
+        assert_eq!(tag_or::<Lit<{ 6 * 7 }>>('-'), '-');
+    }
+}
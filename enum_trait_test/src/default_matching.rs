@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+meta! {
+    pub enum trait Weekday {
+        Monday,
+        Tuesday,
+        Wednesday,
+        Thursday,
+        Friday,
+        Saturday,
+        Sunday,
+    }
+
+    trait impl Weekday {
+        pub type IsWeekend: MetaBool = match <Self> {
+            Saturday => True,
+            Sunday => True,
+            _ => False,
+        };
+
+        pub type Next: Weekday = match <Self> {
+            Monday => Tuesday,
+            Tuesday => Wednesday,
+            Wednesday => Thursday,
+            Thursday => Friday,
+            Friday => Saturday,
+            Saturday => Sunday,
+            Sunday => Monday,
+        };
+
+        pub fn name() -> &'static str {
+            match <Self> {
+                Friday => "friday",
+                _ => "another day",
+            }
+        }
+    }
+
+    pub type IsMonday<D: Weekday>: MetaBool = match <D> {
+        Monday => True,
+        _ => False,
+    };
+
+    pub enum trait Tree {
+        Leaf,
+        Node<L: Tree, R: Tree>,
+    }
+
+    trait impl Tree {
+        pub type IsLeaf: MetaBool = match <Self> {
+            Leaf => True,
+            _ => False,
+        };
+    }
+
+    pub trait DefaultMetaBool = MetaBool;
+
+    trait impl DefaultMetaBool {
+        pub type Not: DefaultMetaBool = match <Self> {
+            False => True,
+            True => False,
+        };
+
+        pub type IsTrue: MetaBool = match <Self> {
+            True => True,
+            _ => False,
+        };
+
+        pub type IsFalse: MetaBool = match <Self> {
+            True => False,
+            False => True,
+        };
+    }
+
+    pub trait WildcardMetaBool = MetaBool;
+
+    pub type IsTrue<B: WildcardMetaBool>: MetaBool = match <B> {
+        True => True,
+        _ => False,
+    };
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(<Monday as Weekday>::IsWeekend, False);
+assert_type_eq!(<Friday as Weekday>::IsWeekend, False);
+assert_type_eq!(<Saturday as Weekday>::IsWeekend, True);
+assert_type_eq!(<Sunday as Weekday>::IsWeekend, True);
+
+assert_type_eq!(<Sunday as Weekday>::Next, Monday);
+assert_type_eq!(<Wednesday as Weekday>::Next, Thursday);
+
+assert_type_eq!(IsMonday<Monday>, True);
+assert_type_eq!(IsMonday<Tuesday>, False);
+assert_type_eq!(IsMonday<<Sunday as Weekday>::Next>, True);
+
+assert_type_eq!(<Leaf as Tree>::IsLeaf, True);
+assert_type_eq!(<Node<Leaf, Leaf> as Tree>::IsLeaf, False);
+
+assert_type_eq!(<False as DefaultMetaBool>::IsTrue, False);
+assert_type_eq!(<True as DefaultMetaBool>::IsTrue, True);
+assert_type_eq!(<False as DefaultMetaBool>::IsFalse, True);
+assert_type_eq!(<True as DefaultMetaBool>::IsFalse, False);
+
+assert_type_eq!(IsTrue<False>, False);
+assert_type_eq!(IsTrue<True>, True);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert_eq!(<Friday as Weekday>::name(), "friday");
+        assert_eq!(<Saturday as Weekday>::name(), "another day");
+        assert_eq!(<Monday as Weekday>::name(), "another day");
+    }
+}
@@ -0,0 +1,97 @@
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+// Typically, the extended trait would be defined in another crate.
+pub mod fruits {
+    use enum_trait::meta;
+
+    use enum_trait_core::meta_bool::*;
+
+    meta! {
+        #[open]
+        pub enum trait Fruit {
+            Apple,
+            Banana,
+        }
+
+        trait impl Fruit {
+            pub const NAME: &'static str = match <Self> {
+                Apple => "apple",
+                Banana => "banana",
+            };
+
+            pub type IsYellow: MetaBool = match <Self> {
+                Banana => True,
+                _ => False,
+            };
+
+            pub fn calories(&self) -> u32 {
+                match <Self> {
+                    Banana => 89,
+                    _ => 52,
+                }
+            }
+
+            pub const IS_BERRY: bool = false;
+        }
+
+        pub fn is_apple<F: Fruit>() -> bool {
+            match <F> {
+                Apple => true,
+                _ => false,
+            }
+        }
+    }
+}
+
+meta! {
+    pub extend enum trait fruits::Fruit {
+        Cherry,
+        Lemon,
+    }
+
+    trait impl Fruit {
+        pub const NAME: &'static str = match <Self> {
+            Cherry => "cherry",
+            Lemon => "lemon",
+        };
+
+        pub type IsYellow: MetaBool = match <Self> {
+            Lemon => True,
+            _ => False,
+        };
+
+        pub const IS_BERRY: bool = match <Self> {
+            Cherry => true,
+            Lemon => false,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fruits::*, *};
+
+    fn is_yellow<F: Fruit>() -> bool {
+        F::IsYellow::VALUE
+    }
+
+    #[test]
+    fn provided_items() {
+        assert_eq!(Cherry::NAME, "cherry");
+        assert_eq!(Lemon::NAME, "lemon");
+        assert!(!is_yellow::<Cherry>());
+        assert!(is_yellow::<Lemon>());
+        assert!(Cherry::IS_BERRY);
+        assert!(!Lemon::IS_BERRY);
+    }
+
+    #[test]
+    fn default_items() {
+        assert_eq!(Cherry(()).calories(), 52);
+        assert!(!is_apple::<Cherry>());
+        assert!(!is_apple::<Lemon>());
+        assert!(is_apple::<Apple>());
+    }
+}
@@ -0,0 +1,128 @@
+use std::{fmt, marker::PhantomData};
+
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+pub trait Describe {
+    const NAME: &'static str;
+    type Opposite;
+}
+
+pub trait ToChar {
+    fn to_char() -> char;
+}
+
+pub trait ListDescription {
+    const DESCRIPTION: &'static str;
+}
+
+meta! {
+    pub enum trait Suit {
+        Hearts,
+        Spades,
+        Joker<T>,
+    }
+
+    trait impl Suit for Describe {
+        const NAME: &'static str = match <Self> {
+            Hearts => "hearts",
+            Spades => "spades",
+            Joker<T> => "joker",
+        };
+
+        type Opposite = match <Self> {
+            Hearts => Spades,
+            Spades => Hearts,
+            Joker<T> => Self,
+        };
+    }
+
+    trait impl Suit for fmt::Display {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match <Self> {
+                Hearts => write!(f, "\u{2665}"),
+                Spades => write!(f, "\u{2660}"),
+                Joker<T> => write!(f, "*"),
+            }
+        }
+    }
+
+    pub enum trait ItemList<'a, trait ItemBound: ?Sized + 'a> {
+        Nil,
+        Cons<Head: ItemBound, Tail: ItemList<'a, ItemBound>>,
+    }
+
+    trait impl<'a, trait ItemBound: ?Sized + 'a> ItemList<'a, ItemBound> for Describe {
+        const NAME: &'static str = match <Self> {
+            Nil => "nil",
+            Cons<Head: ItemBound, Tail: ItemList<'a, ItemBound>> => "cons",
+        };
+
+        type Opposite = Self;
+    }
+
+    pub trait SizedItemList<'a, trait ItemBound: Sized + 'a> = ItemList<'a, ItemBound>;
+
+    trait impl<'a, trait ItemBound: Sized + 'a> SizedItemList<'a, ItemBound> for ListDescription {
+        const DESCRIPTION: &'static str = match <Self> {
+            Nil => "empty",
+            Cons<Head: ItemBound, Tail: SizedItemList<'a, ItemBound>> => "non-empty",
+        };
+    }
+
+    pub trait ForeignMetaBool = MetaBool;
+
+    trait impl ForeignMetaBool for ToChar {
+        fn to_char() -> char {
+            match <Self> {
+                False => '0',
+                True => '1',
+            }
+        }
+    }
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(<Hearts as Describe>::Opposite, Spades);
+assert_type_eq!(<Spades as Describe>::Opposite, Hearts);
+assert_type_eq!(<Joker<u8> as Describe>::Opposite, Joker<u8>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert_eq!(Hearts::NAME, "hearts");
+        assert_eq!(<Joker<()>>::NAME, "joker");
+    }
+
+    #[test]
+    fn generic_names() {
+        assert_eq!(Nil::NAME, "nil");
+        assert_eq!(<Cons<str, Nil>>::NAME, "cons");
+    }
+
+    #[test]
+    fn list_descriptions() {
+        assert_eq!(Nil::DESCRIPTION, "empty");
+        assert_eq!(<Cons<u8, Cons<bool, Nil>>>::DESCRIPTION, "non-empty");
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Spades(()).to_string(), "\u{2660}");
+    }
+
+    #[test]
+    fn to_char() {
+        assert_eq!(False::to_char(), '0');
+        assert_eq!(True::to_char(), '1');
+    }
+}
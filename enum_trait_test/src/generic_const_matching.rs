@@ -0,0 +1,58 @@
+use enum_trait::meta;
+
+meta! {
+    pub enum trait ConstNat {
+        Z,
+        S<N: ConstNat>,
+    }
+
+    trait impl ConstNat {
+        pub const VALUE: usize = match <Self> {
+            Z => 0,
+            S<N: ConstNat> => N::VALUE + 1,
+        };
+    }
+
+    pub enum trait ConstPair {
+        Pair<A: ConstNat, B: ConstNat>,
+    }
+
+    trait impl ConstPair {
+        pub const MIN: usize = match <Self> {
+            Pair<A: ConstNat, B: ConstNat> => match <A, B> {
+                Z, _ => 0,
+                S<_>, Z => 0,
+                S<C: ConstNat>, S<D: ConstNat> => <Pair<C, D> as ConstPair>::MIN + 1,
+            },
+        };
+
+        pub const SUM: usize = match <Self> {
+            Pair<A: ConstNat, B: ConstNat> => match <B> {
+                Z => A::VALUE,
+                S<_> => match <A> {
+                    Z => B::VALUE,
+                    S<C: ConstNat> => C::VALUE + B::VALUE + 1,
+                },
+            },
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min() {
+        assert_eq!(<Pair<Z, S<Z>> as ConstPair>::MIN, 0);
+        assert_eq!(<Pair<S<S<Z>>, S<Z>> as ConstPair>::MIN, 1);
+        assert_eq!(<Pair<S<S<S<Z>>>, S<S<Z>>> as ConstPair>::MIN, 2);
+    }
+
+    #[test]
+    fn sum() {
+        assert_eq!(<Pair<S<S<Z>>, Z> as ConstPair>::SUM, 2);
+        assert_eq!(<Pair<Z, S<Z>> as ConstPair>::SUM, 1);
+        assert_eq!(<Pair<S<S<Z>>, S<Z>> as ConstPair>::SUM, 3);
+    }
+}
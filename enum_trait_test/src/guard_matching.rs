@@ -0,0 +1,99 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, meta_num::*};
+
+meta! {
+    pub trait GuardMetaBool = MetaBool;
+
+    pub enum trait GuardNat {
+        Z,
+        S<N: GuardNat>,
+    }
+
+    pub type Less<A: GuardNat, B: GuardNat>: GuardMetaBool = match <A, B> {
+        _, Z => False,
+        Z, S<_> => True,
+        S<P: GuardNat>, S<Q: GuardNat> => Less<P, Q>,
+    };
+
+    pub type Min<A: GuardNat, B: GuardNat>: GuardNat = match <A> {
+        _ if Less<A, B> => A,
+        _ => B,
+    };
+
+    pub type Clamp<N: GuardNat, Max: GuardNat>: GuardNat = match <N> {
+        S<P: GuardNat> if Less<Max, S<P>> => Max,
+        _ => N,
+    };
+
+    pub trait GuardMetaNum = MetaNum;
+
+    // Guard types must be known to implement `GuardMetaBool`, so `LessThan` cannot be used here,
+    // as its result is only bounded by `MetaBool`.
+    pub type NumLess<M: GuardMetaNum, N: GuardMetaNum>: GuardMetaBool = match <M, N> {
+        _, Zero => False,
+        Zero, Succ<_> => True,
+        Succ<O: MetaNum>, Succ<P: MetaNum> => NumLess<O, P>,
+    };
+
+    pub type ClampNum<N: GuardMetaNum, Max: GuardMetaNum>: GuardMetaNum = match <N> {
+        Succ<P: MetaNum> if NumLess<Max, Succ<P>> => Max,
+        _ => N,
+    };
+
+    pub type MinNum<M: GuardMetaNum, N: GuardMetaNum>: GuardMetaNum = match <M> {
+        Zero => Zero,
+        Succ<P: MetaNum> if NumLess<P, N> => M,
+        Succ<_> => N,
+    };
+
+    pub fn compare<A: GuardNat, B: GuardNat>() -> i8 {
+        match <A, B> {
+            _, _ if Less<A, B> => -1,
+            _, _ if Less<B, A> => 1,
+            _, _ => 0,
+        }
+    }
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(Less<Z, S<Z>>, True);
+assert_type_eq!(Less<S<Z>, S<Z>>, False);
+
+assert_type_eq!(Min<Z, S<Z>>, Z);
+assert_type_eq!(Min<S<S<Z>>, S<Z>>, S<Z>);
+assert_type_eq!(Min<S<Z>, S<Z>>, S<Z>);
+
+assert_type_eq!(Clamp<Z, S<Z>>, Z);
+assert_type_eq!(Clamp<S<Z>, S<Z>>, S<Z>);
+assert_type_eq!(Clamp<S<S<S<Z>>>, S<Z>>, S<Z>);
+
+assert_type_eq!(NumLess<meta_num!(1), meta_num!(2)>, True);
+assert_type_eq!(NumLess<meta_num!(2), meta_num!(2)>, False);
+
+assert_type_eq!(ClampNum<meta_num!(0), meta_num!(1)>, meta_num!(0));
+assert_type_eq!(ClampNum<meta_num!(1), meta_num!(1)>, meta_num!(1));
+assert_type_eq!(ClampNum<meta_num!(3), meta_num!(1)>, meta_num!(1));
+
+assert_type_eq!(MinNum<meta_num!(0), meta_num!(2)>, meta_num!(0));
+assert_type_eq!(MinNum<meta_num!(2), meta_num!(3)>, meta_num!(2));
+assert_type_eq!(MinNum<meta_num!(3), meta_num!(2)>, meta_num!(2));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comparison() {
+        assert_eq!(compare::<Z, S<Z>>(), -1);
+        assert_eq!(compare::<S<S<Z>>, S<Z>>(), 1);
+        assert_eq!(compare::<S<Z>, S<Z>>(), 0);
+    }
+}
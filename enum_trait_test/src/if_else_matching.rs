@@ -0,0 +1,106 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, meta_num::*};
+
+meta! {
+    pub trait IfMetaBool = MetaBool;
+
+    pub trait IfMetaNum = MetaNum;
+
+    pub type Select<B: IfMetaBool, T, U> = if <B> { T } else { U };
+
+    pub type Numbers<B: IfMetaBool> = Vec<if <B> { u16 } else { u8 }>;
+
+    pub type Chain<A: IfMetaBool, B: IfMetaBool> = if <A> { i8 } else if <B> { u8 } else { () };
+
+    pub type ZeroOr<N: IfMetaNum, T> = if <N::IsZero> { () } else { T };
+
+    trait impl IfMetaBool {
+        pub const NAME: &'static str = if <Self> { "yes" } else { "no" };
+    }
+
+    pub fn describe<B: IfMetaBool>(n: u32) -> String {
+        if <B> {
+            format!("{n} is selected")
+        } else {
+            format!("{n} is not selected")
+        }
+    }
+
+    pub fn classify<B: IfMetaBool>(n: u32) -> u32 {
+        let offset = if <B> { 100 } else if n > 10 { 10 } else { 0 };
+        n + offset
+    }
+
+    pub fn as_number<B: IfMetaBool>() -> u8 {
+        // Not a type-level `if`.
+        if <B as MetaBool>::VALUE { 1 } else { 0 }
+    }
+
+    pub fn push_if<B: IfMetaBool>(values: &mut Vec<u32>, value: u32) {
+        if <B> {
+            values.push(value);
+        } else {
+            values.clear();
+        }
+        values.push(0);
+    }
+
+    pub fn is_zero<N: IfMetaNum>() -> bool
+    where
+        N::IsZero: IfMetaBool,
+    {
+        if <N::IsZero> {
+            true
+        } else {
+            false
+        }
+    }
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(Select<False, u8, u16>, u16);
+assert_type_eq!(Select<True, u8, u16>, u8);
+assert_type_eq!(Numbers<False>, Vec<u8>);
+assert_type_eq!(Numbers<True>, Vec<u16>);
+assert_type_eq!(Chain<True, False>, i8);
+assert_type_eq!(Chain<False, True>, u8);
+assert_type_eq!(Chain<False, False>, ());
+assert_type_eq!(ZeroOr<meta_num!(0), u8>, ());
+assert_type_eq!(ZeroOr<meta_num!(1), u8>, u8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consts() {
+        assert_eq!(False::NAME, "no");
+        assert_eq!(True::NAME, "yes");
+    }
+
+    #[test]
+    fn fn_bodies() {
+        assert_eq!(describe::<False>(1), "1 is not selected");
+        assert_eq!(describe::<True>(1), "1 is selected");
+        assert_eq!(classify::<False>(5), 5);
+        assert_eq!(classify::<False>(20), 30);
+        assert_eq!(classify::<True>(20), 120);
+        assert_eq!(as_number::<False>(), 0);
+        assert_eq!(as_number::<True>(), 1);
+        let mut values = vec![1];
+        push_if::<True>(&mut values, 2);
+        assert_eq!(values, [1, 2, 0]);
+        push_if::<False>(&mut values, 3);
+        assert_eq!(values, [0]);
+        assert!(is_zero::<meta_num!(0)>());
+        assert!(!is_zero::<meta_num!(2)>());
+    }
+}
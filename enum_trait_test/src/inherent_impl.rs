@@ -0,0 +1,67 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+pub struct Wrapper<B, T> {
+    first: T,
+    second: T,
+    _marker: PhantomData<B>,
+}
+
+meta! {
+    pub trait ImplMetaBool = MetaBool;
+
+    impl<B: ImplMetaBool, T> Wrapper<B, T> {
+        pub const INDEX: usize = match <B> {
+            False => 0,
+            True => 1,
+        };
+
+        pub fn new(first: T, second: T) -> Self {
+            Wrapper {
+                first,
+                second,
+                _marker: PhantomData,
+            }
+        }
+
+        pub fn get(&self) -> &T {
+            match <B> {
+                False => &self.first,
+                True => &self.second,
+            }
+        }
+
+        pub fn into_inner(self) -> T {
+            match <B> {
+                False => self.first,
+                True => self.second,
+            }
+        }
+
+        pub fn swapped(self) -> Self {
+            match <B> {
+                False => Self::new(self.second, self.first),
+                True => self,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inherent_impl() {
+        assert_eq!(Wrapper::<False, u8>::INDEX, 0);
+        assert_eq!(Wrapper::<True, u8>::INDEX, 1);
+        assert_eq!(*Wrapper::<False, _>::new(1, 2).get(), 1);
+        assert_eq!(*Wrapper::<True, _>::new(1, 2).get(), 2);
+        assert_eq!(Wrapper::<True, _>::new(1, 2).into_inner(), 2);
+        assert_eq!(Wrapper::<False, _>::new(1, 2).swapped().into_inner(), 2);
+        assert_eq!(Wrapper::<True, _>::new(1, 2).swapped().into_inner(), 2);
+    }
+}
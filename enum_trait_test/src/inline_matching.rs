@@ -0,0 +1,65 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+meta! {
+    pub trait InlineMetaBool = MetaBool;
+
+    pub type MaybeOptions<B: InlineMetaBool, T> = Vec<match <B> {
+        False => T,
+        True => Option<T>,
+    }>;
+
+    pub type Nested<A: InlineMetaBool, B: InlineMetaBool> = (
+        match <A> {
+            False => u8,
+            True => Box<match <B> {
+                False => u16,
+                True => u32,
+            }>,
+        },
+        fn(match <B> { False => i8, True => i16 }) -> bool,
+    );
+
+    pub fn first_or_default<B: InlineMetaBool>(
+        values: &[match <B> { False => u32, True => Option<u32> }],
+    ) -> u32 {
+        match <B> {
+            False => values.first().copied().unwrap_or_default(),
+            True => values.first().copied().flatten().unwrap_or_default(),
+        }
+    }
+
+    pub fn wrap<B: InlineMetaBool>(value: u32) -> Option<match <B> { False => u32, True => Vec<u32> }> {
+        match <B> {
+            False => Some(value),
+            True => Some(vec![value]),
+        }
+    }
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(MaybeOptions<False, u8>, Vec<u8>);
+assert_type_eq!(MaybeOptions<True, u8>, Vec<Option<u8>>);
+assert_type_eq!(Nested<False, True>, (u8, fn(i16) -> bool));
+assert_type_eq!(Nested<True, False>, (Box<u16>, fn(i8) -> bool));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures() {
+        assert_eq!(first_or_default::<False>(&[3, 4]), 3);
+        assert_eq!(first_or_default::<True>(&[None, Some(4)]), 0);
+        assert_eq!(wrap::<False>(1), Some(1));
+        assert_eq!(wrap::<True>(1), Some(vec![1]));
+    }
+}
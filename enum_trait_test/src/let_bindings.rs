@@ -0,0 +1,66 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, meta_num::*};
+
+meta! {
+    pub trait LetMetaBool = MetaBool;
+
+    pub trait LetMetaNum = MetaNum;
+
+    pub type Pair<T> = let Item = Option<T>; in (Item, Item);
+
+    pub type HalfIsEven<N: LetMetaNum>: MetaBool = let Half: MetaNum = Div2<N>; in Half::IsEven;
+
+    pub type HalfOf<N: LetMetaNum>: MetaNum = Div2<N>;
+
+    // The trait of `Half` is inferred from the declaration of `HalfOf`.
+    pub type HalfIsOdd<N: LetMetaNum>: MetaBool = let Half = HalfOf<N>; in Not<Half::IsEven>;
+
+    pub type Quadruple<N: LetMetaNum>: MetaNum =
+        let Double = Add<N, N>;
+        let Quadruple = Add<Double, Double>;
+        in Quadruple;
+
+    pub type PredOrZero<N: LetMetaNum>: MetaNum = match <N> {
+        Zero => Zero,
+        Succ<P: MetaNum> => let Result = P; in Result,
+    };
+
+    pub type Choose<B: LetMetaBool, T> = let Wrapped = match <B> {
+        False => T,
+        True => Option<T>,
+    }; in Vec<Wrapped>;
+
+    trait impl LetMetaNum {
+        pub const DOUBLE_VALUE: usize = let Double: MetaNum = Add<Self, Self>; in Double::VALUE;
+    }
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(Pair<u8>, (Option<u8>, Option<u8>));
+assert_type_eq!(Choose<False, u8>, Vec<u8>);
+assert_type_eq!(Choose<True, u8>, Vec<Option<u8>>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings() {
+        assert!(<HalfIsEven<meta_num!(4)>>::VALUE);
+        assert!(!<HalfIsEven<meta_num!(2)>>::VALUE);
+        assert!(!<HalfIsOdd<meta_num!(4)>>::VALUE);
+        assert!(<HalfIsOdd<meta_num!(2)>>::VALUE);
+        assert_eq!(<Quadruple<meta_num!(3)>>::VALUE, 12);
+        assert_eq!(<PredOrZero<meta_num!(0)>>::VALUE, 0);
+        assert_eq!(<PredOrZero<meta_num!(3)>>::VALUE, 2);
+        assert_eq!(<meta_num!(3)>::DOUBLE_VALUE, 6);
+    }
+}
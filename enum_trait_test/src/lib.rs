@@ -1,7 +1,33 @@
+pub mod arm_order;
+pub mod body_matching;
+pub mod combined_matching;
+pub mod compound_matching;
+pub mod const_params;
+pub mod default_matching;
+pub mod extended_traits;
+pub mod foreign_trait_impl;
+pub mod generic_const_matching;
+pub mod guard_matching;
+pub mod if_else_matching;
+pub mod inherent_impl;
+pub mod inline_matching;
+pub mod let_bindings;
+pub mod lifetime_params;
 pub mod meta_bool_alias;
 pub mod meta_bool_alias_alias;
 pub mod meta_bool_matching;
 pub mod meta_bool_matching_matching;
 pub mod meta_bool_matching_with_path;
 pub mod meta_num_macro;
+pub mod nested_matching;
+pub mod number_literals;
+pub mod open_traits;
+pub mod operator_exprs;
+pub mod or_matching;
+pub mod pattern_params;
+pub mod receiver_matching;
+pub mod struct_matching;
+pub mod trait_impl_matching;
 pub mod unusual_matching;
+pub mod upstream_extensions;
+pub mod where_clauses;
@@ -0,0 +1,59 @@
+use enum_trait::meta;
+
+use enum_trait_core::meta_num::*;
+
+meta! {
+    pub enum trait RefList<'a, trait ItemBound: ?Sized + 'a> {
+        Empty,
+        NonEmpty<Head: ItemBound, Tail: RefList<'a, ItemBound>>,
+    }
+
+    trait impl<'a, trait ItemBound: ?Sized + 'a> RefList<'a, ItemBound> {
+        pub type Len: MetaNum = match <Self> {
+            Empty => Zero,
+            NonEmpty<Head: ItemBound, Tail: RefList<'a, ItemBound>> => Succ<Tail::Len>,
+        };
+
+        pub type MapToRefs<'b>: SizedRefList<'b> where 'a: 'b = match <Self> {
+            Empty => Empty,
+            NonEmpty<Head: ItemBound, Tail: RefList<'a, ItemBound>> =>
+                NonEmpty<&'b Head, <Tail as RefList<'a, ItemBound>>::MapToRefs<'b>>,
+        };
+    }
+
+    pub trait SizedRefList<'a, trait ItemBound: Sized + 'a> = RefList<'a, ItemBound>;
+
+    pub type NestedTuple<'a, trait ItemBound: Sized + 'a, List: SizedRefList<'a, ItemBound>>: Sized =
+        match <List> {
+            Empty => (),
+            NonEmpty<Head: ItemBound, Tail: SizedRefList<'a, ItemBound>> =>
+                (Head, NestedTuple<'a, ItemBound, Tail>),
+        };
+
+    pub fn nested_len<'a, trait ItemBound: Sized + 'a, List: SizedRefList<'a, ItemBound>>(
+        tuple: &NestedTuple<'a, ItemBound, List>,
+    ) -> usize {
+        match <List> {
+            Empty => 0,
+            NonEmpty<Head: ItemBound, Tail: SizedRefList<'a, ItemBound>> =>
+                1 + nested_len::<ItemBound, Tail>(&tuple.1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Two<'a> = NonEmpty<&'a str, NonEmpty<u8, Empty>>;
+
+    #[test]
+    fn lifetimes() {
+        assert_eq!(<<Two as RefList>::Len as MetaNum>::VALUE, 2);
+        assert_eq!(
+            <<<Two as RefList>::MapToRefs<'_> as RefList>::Len as MetaNum>::VALUE,
+            2
+        );
+        assert_eq!(nested_len::<Two>(&("test", (42, ()))), 2);
+    }
+}
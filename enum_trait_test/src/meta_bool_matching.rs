@@ -7,8 +7,8 @@ meta! {
 
     trait impl MetaBoolWithNumConv {
         pub type ToMetaNum: MetaNum = match <Self> {
-            False => meta_num!(0),
-            True => meta_num!(1),
+            False => 0,
+            True => 1,
         };
     }
 }
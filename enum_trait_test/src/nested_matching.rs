@@ -0,0 +1,98 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, meta_num::*};
+
+meta! {
+    pub enum trait NestedNat {
+        Z,
+        S<N: NestedNat>,
+    }
+
+    trait impl NestedNat {
+        pub type Div3: NestedNat = match <Self> {
+            S<S<S<N: NestedNat>>> => S<N::Div3>,
+            _ => Z,
+        };
+
+        pub type IsOne: MetaBool = match <Self> {
+            S<Z> => True,
+            _ => False,
+        };
+
+        pub type IsPositive: MetaBool = match <Self> {
+            Z => False,
+            S<N: NestedNat> => True,
+        };
+    }
+
+    pub trait PositiveNat = NestedNat where Self::IsPositive = True;
+
+    pub type IsAtLeastTwo<N: PositiveNat>: MetaBool = match <N> {
+        S<S<M: NestedNat>> => True,
+        S<N: NestedNat> => False,
+    };
+
+    pub trait NestedMetaNum = MetaNum;
+
+    pub type IsTwo<N: NestedMetaNum>: MetaBool = match <N> {
+        Succ<Succ<Zero>> => True,
+        _ => False,
+    };
+
+    pub enum trait NestedList {
+        Nil,
+        Cons<Item, Tail: NestedList>,
+    }
+
+    trait impl NestedList {
+        pub type Pairs: NestedList = match <Self> {
+            Cons<A, Cons<B, Tail: NestedList>> => Cons<(A, B), Tail::Pairs>,
+            _ => Nil,
+        };
+
+        pub type IsSingleton: MetaBool = match <Self> {
+            Cons<Item, Nil> => True,
+            _ => False,
+        };
+    }
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(<Z as NestedNat>::Div3, Z);
+assert_type_eq!(<S<S<Z>> as NestedNat>::Div3, Z);
+assert_type_eq!(<S<S<S<Z>>> as NestedNat>::Div3, S<Z>);
+assert_type_eq!(<S<S<S<S<S<S<S<Z>>>>>>> as NestedNat>::Div3, S<S<Z>>);
+
+assert_type_eq!(<Z as NestedNat>::IsOne, False);
+assert_type_eq!(<S<Z> as NestedNat>::IsOne, True);
+assert_type_eq!(<S<S<Z>> as NestedNat>::IsOne, False);
+
+assert_type_eq!(IsAtLeastTwo<S<Z>>, False);
+assert_type_eq!(IsAtLeastTwo<S<S<Z>>>, True);
+
+assert_type_eq!(IsTwo<Zero>, False);
+assert_type_eq!(IsTwo<Succ<Zero>>, False);
+assert_type_eq!(IsTwo<Succ<Succ<Zero>>>, True);
+assert_type_eq!(IsTwo<Succ<Succ<Succ<Zero>>>>, False);
+
+assert_type_eq!(<Nil as NestedList>::Pairs, Nil);
+assert_type_eq!(<Cons<u8, Nil> as NestedList>::Pairs, Nil);
+assert_type_eq!(
+    <Cons<u8, Cons<u16, Cons<u32, Nil>>> as NestedList>::Pairs,
+    Cons<(u8, u16), Nil>,
+);
+assert_type_eq!(
+    <Cons<u8, Cons<u16, Cons<u32, Cons<u64, Nil>>>> as NestedList>::Pairs,
+    Cons<(u8, u16), Cons<(u32, u64), Nil>>,
+);
+
+assert_type_eq!(<Nil as NestedList>::IsSingleton, False);
+assert_type_eq!(<Cons<u8, Nil> as NestedList>::IsSingleton, True);
+assert_type_eq!(<Cons<u8, Cons<u16, Nil>> as NestedList>::IsSingleton, False);
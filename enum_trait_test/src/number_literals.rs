@@ -0,0 +1,61 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::meta_num::{self, *};
+
+meta! {
+    pub type Three: MetaNum = 3;
+
+    pub type Sum: MetaNum = 2 + 3n;
+
+    pub type Wrapped = Vec<PhantomData<3n>>;
+
+    pub type Array<T> = [T; 3];
+}
+
+pub struct Const<const N: usize>;
+
+meta! {
+    pub type ConstArg = Const<3>;
+}
+
+meta! {
+    #![numbers(zero = meta_num::Zero, succ = meta_num::Succ)]
+
+    pub type Qualified: MetaNum = 2;
+}
+
+pub struct MyZero;
+
+pub struct MySucc<N>(PhantomData<N>);
+
+meta! {
+    #![numbers(zero = MyZero, succ = MySucc)]
+
+    pub type Custom = 2;
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(Three, Succ<Succ<Succ<Zero>>>);
+assert_type_eq!(Wrapped, Vec<PhantomData<Succ<Succ<Succ<Zero>>>>>);
+assert_type_eq!(Array<u8>, [u8; 3]);
+assert_type_eq!(ConstArg, Const<3>);
+assert_type_eq!(Qualified, Succ<Succ<Zero>>);
+assert_type_eq!(Custom, MySucc<MySucc<MyZero>>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literals() {
+        assert_eq!(<Three>::VALUE, 3);
+        assert_eq!(<Sum>::VALUE, 5);
+    }
+}
@@ -0,0 +1,36 @@
+use enum_trait::meta;
+
+meta! {
+    #[open]
+    pub enum trait Polygon {
+        Pentagon,
+        Square,
+    }
+
+    trait impl Polygon {
+        pub const CORNERS: usize = match <Self> {
+            Pentagon => 5,
+            Square => 4,
+        };
+    }
+}
+
+// Open enum traits can be implemented outside of `meta!` blocks, e.g. in other crates.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Triangle;
+
+impl Polygon for Triangle {
+    const CORNERS: usize = 3;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corners() {
+        assert_eq!(Pentagon::CORNERS, 5);
+        assert_eq!(Square::CORNERS, 4);
+        assert_eq!(Triangle::CORNERS, 3);
+    }
+}
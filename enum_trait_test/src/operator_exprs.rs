@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, meta_num::*};
+
+meta! {
+    pub trait OpMetaNum = MetaNum;
+
+    pub type Polynomial<N: MetaNum>: MetaNum = N * N + 1 * N + 1;
+
+    pub type Grouped<N: MetaNum>: MetaNum = (N + 1) * (N + 1);
+
+    pub type InRange<N: MetaNum, Min: MetaNum, Max: MetaNum>: MetaBool =
+        !(N < Min) && N < Max || N == Min;
+
+    pub type IsSmall<N: OpMetaNum>: MetaBool = match <N> {
+        Zero => True,
+        Succ<P: MetaNum> => P < 2,
+    };
+
+    pub type Unit = ();
+
+    pub type Tuple<N: MetaNum> = (N + N, Option<N * N>);
+}
+
+mod shadowed {
+    // The default operator paths are not affected by types of the same name in scope.
+    #[allow(unused_imports)]
+    use std::ops::{Add, Not};
+
+    use enum_trait::meta;
+
+    use enum_trait_core::meta_num::MetaNum;
+
+    meta! {
+        pub type IsEmpty<N: MetaNum> = !(0 < N + N * 2);
+    }
+}
+
+pub type Xor<A, B> = PhantomData<(A, B)>;
+
+meta! {
+    #![operators(add = Xor, eq = enum_trait_core::meta_bool::Equal)]
+
+    pub type XorPair<A: MetaBool, B: MetaBool> = A + B;
+
+    pub type BoolEq<A: MetaBool, B: MetaBool>: MetaBool = A == !B;
+}
+
+meta! {
+    #![operators(lt = OpLess, eq = OpEqual)]
+
+    pub trait OpMetaBool = MetaBool;
+
+    pub enum trait OpNat {
+        Z,
+        S<N: OpNat>,
+    }
+
+    pub type OpLess<A: OpNat, B: OpNat>: OpMetaBool = match <A, B> {
+        _, Z => False,
+        Z, S<_> => True,
+        S<P: OpNat>, S<Q: OpNat> => P < Q,
+    };
+
+    pub type OpEqual<A: OpNat, B: OpNat>: OpMetaBool = match <A, B> {
+        Z, Z => True,
+        S<P: OpNat>, S<Q: OpNat> => P == Q,
+        _, _ => False,
+    };
+
+    pub type Smaller<M: OpNat, N: OpNat>: OpNat = if <M < N> { M } else { N };
+
+    pub type IsOneOf<N: OpNat, A: OpNat, B: OpNat>: OpMetaBool = if <N == A> {
+        True
+    } else if <(N == B)> {
+        True
+    } else {
+        False
+    };
+
+    pub type Greater<M: OpNat, N: OpNat>: OpMetaBool = match <M < N, N < M> {
+        False, False => False,
+        False, True => True,
+        True, _ => False,
+    };
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(Unit, ());
+assert_type_eq!(
+    Tuple<Succ<Zero>>,
+    (
+        Add<Succ<Zero>, Succ<Zero>>,
+        Option<Mul<Succ<Zero>, Succ<Zero>>>
+    ),
+);
+assert_type_eq!(XorPair<True, False>, Xor<True, False>);
+assert_type_eq!(
+    shadowed::IsEmpty<Zero>,
+    Not<LessThan<Zero, Add<Zero, Mul<Zero, Succ<Succ<Zero>>>>>>,
+);
+
+assert_type_eq!(Smaller<S<Z>, S<S<Z>>>, S<Z>);
+assert_type_eq!(Smaller<S<S<Z>>, Z>, Z);
+assert_type_eq!(IsOneOf<S<Z>, Z, S<Z>>, True);
+assert_type_eq!(IsOneOf<Z, Z, S<Z>>, True);
+assert_type_eq!(IsOneOf<S<S<Z>>, Z, S<Z>>, False);
+assert_type_eq!(Greater<S<S<Z>>, S<Z>>, True);
+assert_type_eq!(Greater<S<Z>, S<S<Z>>>, False);
+assert_type_eq!(Greater<S<Z>, S<Z>>, False);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(<Polynomial<meta_num!(0)>>::VALUE, 1);
+        assert_eq!(<Polynomial<meta_num!(3)>>::VALUE, 13);
+        assert_eq!(<Grouped<meta_num!(2)>>::VALUE, 9);
+    }
+
+    #[test]
+    fn comparisons() {
+        assert!(<InRange<meta_num!(2), meta_num!(1), meta_num!(3)>>::VALUE);
+        assert!(!<InRange<meta_num!(3), meta_num!(1), meta_num!(3)>>::VALUE);
+        assert!(!<InRange<meta_num!(0), meta_num!(1), meta_num!(3)>>::VALUE);
+        assert!(<InRange<meta_num!(1), meta_num!(1), meta_num!(1)>>::VALUE);
+        assert!(<IsSmall<meta_num!(2)>>::VALUE);
+        assert!(!<IsSmall<meta_num!(3)>>::VALUE);
+    }
+
+    #[test]
+    fn custom_operators() {
+        assert!(<BoolEq<True, False>>::VALUE);
+        assert!(!<BoolEq<True, True>>::VALUE);
+    }
+}
@@ -0,0 +1,90 @@
+use std::marker::PhantomData;
+
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, meta_num::*};
+
+meta! {
+    pub enum trait Shade {
+        Red,
+        Green,
+        Blue,
+        Black,
+        White,
+    }
+
+    trait impl Shade {
+        pub type IsPrimary: MetaBool = match <Self> {
+            Red | Green | Blue => True,
+            Black | White => False,
+        };
+
+        pub fn is_gray() -> bool {
+            match <Self> {
+                Black | White => true,
+                _ => false,
+            }
+        }
+    }
+
+    pub trait OrMetaBool = MetaBool;
+
+    pub type Implies<A: OrMetaBool, B: OrMetaBool>: MetaBool = match <A, B> {
+        False, False | True => True,
+        True, False => False,
+        True, True => True,
+    };
+
+    pub enum trait OrNat {
+        Z,
+        S<N: OrNat>,
+    }
+
+    pub type IsSmall<N: OrNat>: MetaBool = match <N> {
+        Z | S<Z | S<Z>> => True,
+        S<S<S<_>>> => False,
+    };
+
+    pub type BothPositive<A: OrNat, B: OrNat>: MetaBool = match <A, B> {
+        S<_>, S<_> => True,
+        _, _ => False,
+    };
+}
+
+macro_rules! assert_type_eq {
+    ($X:ty, $Y:ty $(,)?) => {
+        const _: PhantomData<$X> = PhantomData::<$Y>;
+    };
+}
+
+assert_type_eq!(<Red as Shade>::IsPrimary, True);
+assert_type_eq!(<Blue as Shade>::IsPrimary, True);
+assert_type_eq!(<White as Shade>::IsPrimary, False);
+
+assert_type_eq!(Implies<False, False>, True);
+assert_type_eq!(Implies<False, True>, True);
+assert_type_eq!(Implies<True, False>, False);
+assert_type_eq!(Implies<True, True>, True);
+
+assert_type_eq!(IsSmall<Z>, True);
+assert_type_eq!(IsSmall<S<S<Z>>>, True);
+assert_type_eq!(IsSmall<S<S<S<Z>>>>, False);
+
+assert_type_eq!(BothPositive<Z, S<Z>>, False);
+assert_type_eq!(BothPositive<S<Z>, Z>, False);
+assert_type_eq!(BothPositive<S<Z>, S<S<Z>>>, True);
+
+assert_type_eq!(<Zero as MetaNum>::IsZero, True);
+assert_type_eq!(<Succ<Zero> as MetaNum>::IsZero, False);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gray() {
+        assert!(<Black as Shade>::is_gray());
+        assert!(<White as Shade>::is_gray());
+        assert!(!<Green as Shade>::is_gray());
+    }
+}
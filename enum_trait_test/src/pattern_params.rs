@@ -0,0 +1,85 @@
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, ref_kind::*};
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+meta! {
+    pub trait PatternMetaBool = MetaBool;
+
+    pub trait PatternRefKind = RefKind;
+
+    pub fn pick<B: PatternMetaBool, T>((first, second): (T, T), _: u8) -> T {
+        match <B> {
+            False => first,
+            True => second,
+        }
+    }
+
+    pub fn coordinate<B: PatternMetaBool>(Point { x, y }: Point) -> i32 {
+        match <B> {
+            False => x,
+            True => y,
+        }
+    }
+
+    pub fn split<'a, K: PatternRefKind, A, B>(
+        (a, b): K::Ref<'a, (A, B)>,
+    ) -> (K::Ref<'a, A>, K::Ref<'a, B>) {
+        match <K> {
+            Shared => (a, b),
+            Mutable => (a, b),
+        }
+    }
+
+    trait impl PatternMetaBool {
+        pub fn sum_if((a, b): (u32, u32)) -> u32 {
+            match <Self> {
+                False => a,
+                True => a + b,
+            }
+        }
+
+        pub fn first_or((a, _): (u32, u32), default: u32) -> u32 {
+            match <Self> {
+                False => default,
+                True => a,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tuple_patterns() {
+        assert_eq!(pick::<False, _>((1, 2), 0), 1);
+        assert_eq!(pick::<True, _>((1, 2), 0), 2);
+        assert_eq!(<False as PatternMetaBool>::sum_if((1, 2)), 1);
+        assert_eq!(<True as PatternMetaBool>::sum_if((1, 2)), 3);
+        assert_eq!(<False as PatternMetaBool>::first_or((1, 2), 5), 5);
+        assert_eq!(<True as PatternMetaBool>::first_or((1, 2), 5), 1);
+    }
+
+    #[test]
+    fn struct_patterns() {
+        assert_eq!(coordinate::<False>(Point { x: 1, y: 2 }), 1);
+        assert_eq!(coordinate::<True>(Point { x: 1, y: 2 }), 2);
+    }
+
+    #[test]
+    fn ref_patterns() {
+        let mut pair = (1, 2);
+        let (a, b) = split::<Shared, _, _>(&pair);
+        assert_eq!((*a, *b), (1, 2));
+        let (a, b) = split::<Mutable, _, _>(&mut pair);
+        *a += 1;
+        *b += 1;
+        assert_eq!(pair, (2, 3));
+    }
+}
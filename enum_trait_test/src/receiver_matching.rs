@@ -0,0 +1,61 @@
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+meta! {
+    pub enum trait Shape {
+        Circle,
+        Square,
+    }
+
+    pub trait ReceiverMetaBool = MetaBool;
+
+    trait impl Shape {
+        pub fn factor(&self) -> u32 {
+            match <Self> {
+                Circle => 3,
+                Square => 4,
+            }
+        }
+
+        pub fn scaled<B: ReceiverMetaBool>(&self, value: u32) -> u32 {
+            match <B> {
+                False => value,
+                True => self.factor() * value,
+            }
+        }
+
+        pub fn describe<B: ReceiverMetaBool>(self) -> String {
+            match <B> {
+                False => String::new(),
+                True => format!("{}", self.factor()),
+            }
+        }
+
+        pub fn mut_factor<B: ReceiverMetaBool>(mut self) -> u32 {
+            match <B> {
+                False => 0,
+                True => {
+                    let receiver = &mut self;
+                    receiver.factor()
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receivers() {
+        assert_eq!(Circle(()).factor(), 3);
+        assert_eq!(Square(()).scaled::<False>(5), 5);
+        assert_eq!(Square(()).scaled::<True>(5), 20);
+        assert_eq!(Circle(()).describe::<False>(), "");
+        assert_eq!(Circle(()).describe::<True>(), "3");
+        assert_eq!(Square(()).mut_factor::<False>(), 0);
+        assert_eq!(Square(()).mut_factor::<True>(), 4);
+    }
+}
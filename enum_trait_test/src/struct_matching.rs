@@ -0,0 +1,91 @@
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+meta! {
+    pub trait StructMetaBool = MetaBool;
+
+    pub struct Counter<B: StructMetaBool> {
+        pub count: match <B> {
+            False => u8,
+            True => u64,
+        },
+        pub step: u8,
+    }
+
+    pub struct Tagged<B: StructMetaBool, T>(pub match <B> {
+        False => T,
+        True => Option<T>,
+    });
+
+    pub enum Value<B: StructMetaBool, T> {
+        Empty,
+        Single(match <B> {
+            False => T,
+            True => Box<T>,
+        }),
+        Pair {
+            first: T,
+            second: match <B> {
+                False => T,
+                True => Vec<T>,
+            },
+        },
+    }
+
+    // Derived impls would not require the field types to implement the trait.
+    impl<B: StructMetaBool> std::fmt::Debug for Counter<B> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let count = match <B> {
+                False => u64::from(self.count),
+                True => self.count,
+            };
+            f.debug_struct("Counter")
+                .field("count", &count)
+                .field("step", &self.step)
+                .finish()
+        }
+    }
+
+    pub fn increment<B: StructMetaBool>(counter: &mut Counter<B>) {
+        match <B> {
+            False => counter.count = counter.count.wrapping_add(counter.step),
+            True => counter.count += u64::from(counter.step),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_fields() {
+        let mut small = Counter::<False> {
+            count: 250,
+            step: 10,
+        };
+        increment(&mut small);
+        assert_eq!(small.count, 4u8);
+        let mut large = Counter::<True> {
+            count: 250,
+            step: 10,
+        };
+        increment(&mut large);
+        assert_eq!(large.count, 260u64);
+
+        assert_eq!(format!("{large:?}"), "Counter { count: 260, step: 10 }");
+
+        let Tagged(tagged) = Tagged::<True, u8>(Some(1));
+        assert_eq!(tagged, Some(1));
+
+        let single = Value::<True, u8>::Single(Box::new(5));
+        assert!(matches!(single, Value::Single(value) if *value == 5));
+        let pair = Value::<True, u8>::Pair {
+            first: 1,
+            second: vec![2, 3],
+        };
+        assert!(matches!(pair, Value::Pair { second, .. } if second == [2, 3]));
+        assert!(matches!(Value::<False, u8>::Empty, Value::Empty));
+    }
+}
@@ -0,0 +1,63 @@
+use std::{fmt, marker::PhantomData, ops::Add};
+
+use enum_trait::meta;
+
+use enum_trait_core::meta_bool::*;
+
+pub struct Labeled<B, T> {
+    value: T,
+    _marker: PhantomData<B>,
+}
+
+impl<B, T> Labeled<B, T> {
+    pub fn new(value: T) -> Self {
+        Labeled {
+            value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+meta! {
+    pub trait TraitImplMetaBool = MetaBool;
+
+    impl<B: TraitImplMetaBool, T: fmt::Display> fmt::Display for Labeled<B, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match <B> {
+                False => write!(f, "{}", self.value),
+                True => write!(f, "value: {}", self.value),
+            }
+        }
+    }
+
+    impl<B: TraitImplMetaBool> Add<u32> for Labeled<B, u32> {
+        type Output = match <B> {
+            False => u32,
+            True => Self,
+        };
+
+        fn add(self, rhs: u32) -> Self::Output {
+            match <B> {
+                False => self.value + rhs,
+                True => Labeled::new(self.value + rhs),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Labeled::<False, _>::new(42).to_string(), "42");
+        assert_eq!(Labeled::<True, _>::new(42).to_string(), "value: 42");
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(Labeled::<False, u32>::new(1) + 2, 3);
+        assert_eq!((Labeled::<True, u32>::new(1) + 2).value, 3);
+    }
+}
@@ -0,0 +1,48 @@
+use enum_trait::meta;
+
+use enum_trait_test_upstream::shapes;
+
+meta! {
+    pub extend enum trait shapes::Shape {
+        Hexagon,
+        Triangle,
+    }
+
+    trait impl Shape {
+        pub fn sides() -> u32 {
+            match <Self> {
+                Hexagon => 6,
+                Triangle => 3,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use enum_trait_core::meta_bool::MetaBool;
+
+    use super::{shapes::*, *};
+
+    fn is_round<S: Shape>() -> bool {
+        S::IsRound::VALUE
+    }
+
+    #[test]
+    fn provided_items() {
+        assert_eq!(Hexagon::sides(), 6);
+        assert_eq!(Triangle::sides(), 3);
+    }
+
+    #[test]
+    fn default_items() {
+        assert!(!is_round::<Hexagon>());
+        assert!(is_round::<Circle>());
+        assert_eq!(Triangle(()).perimeter(2), 6);
+        assert_eq!(Triangle(()).scaled_perimeter((2, 3)), 18);
+        assert_eq!(Hexagon::CORNERS, 6);
+        let _: <Triangle as Shape>::Corners<u8> = [0; 6];
+        assert!(!is_square::<Triangle>());
+        assert!(is_square::<Square>());
+    }
+}
@@ -0,0 +1,45 @@
+use enum_trait::meta;
+
+use enum_trait_core::{meta_bool::*, meta_num::*};
+
+meta! {
+    pub enum trait Signal {
+        Red,
+        Amber,
+        Green,
+    }
+
+    trait impl Signal {
+        pub type AllowsPassing: MetaBool = match <Self> {
+            Green => True,
+            _ => False,
+        };
+    }
+
+    // The type equality is checked when implementing `PassingSignal` for `Green`.
+    pub trait PassingSignal = Signal where Self::AllowsPassing = True;
+
+    pub fn message<S: PassingSignal>() -> &'static str {
+        match <S> {
+            Green => "go",
+        }
+    }
+
+    pub trait Positive = MetaNum where Self::IsZero = False;
+
+    pub type Prev<N: Positive>: MetaNum = match <N> {
+        Succ<P: MetaNum> => P,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_equalities() {
+        assert_eq!(message::<Green>(), "go");
+        assert_eq!(<Prev<Succ<Zero>>>::VALUE, 0);
+        assert_eq!(<Prev<Succ<Succ<Zero>>>>::VALUE, 1);
+    }
+}
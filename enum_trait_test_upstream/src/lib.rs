@@ -0,0 +1,64 @@
+// An open enum trait whose default items refer to names that are private to this crate, so that
+// `enum_trait_test` can check that extensions in other crates work.
+pub mod shapes {
+    use enum_trait::meta;
+
+    use enum_trait_core::meta_bool::*;
+
+    const fn default_sides() -> u32 {
+        6
+    }
+
+    meta! {
+        #[open]
+        pub enum trait Shape {
+            Circle,
+            Square,
+        }
+
+        trait impl Shape {
+            pub type IsRound: MetaBool = match <Self> {
+                Circle => True,
+                _ => False,
+            };
+
+            pub type Corners<T>: Sized = match <Self> {
+                Circle => (),
+                _ => [T; default_sides() as usize],
+            };
+
+            pub fn sides() -> u32 {
+                match <Self> {
+                    Circle => 0,
+                    _ => default_sides(),
+                }
+            }
+
+            pub fn perimeter(&self, side: u32) -> u32 {
+                match <Self> {
+                    Circle => 0,
+                    _ => Self::sides() * side,
+                }
+            }
+
+            pub fn scaled_perimeter(&self, (side, factor): (u32, u32)) -> u32 {
+                match <Self> {
+                    Circle => 0,
+                    _ => self.perimeter(side) * factor,
+                }
+            }
+
+            pub const CORNERS: u32 = match <Self> {
+                Circle => 0,
+                _ => default_sides(),
+            };
+        }
+
+        pub fn is_square<S: Shape>() -> bool {
+            match <S> {
+                Square => true,
+                _ => false,
+            }
+        }
+    }
+}